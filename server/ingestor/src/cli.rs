@@ -0,0 +1,189 @@
+//! Command-line entry points besides the default ingestion cycle
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+
+use crate::sources::archive::{parse_since, BackfillOptions};
+
+pub const USAGE: &str = "Usage:
+  ingestor                              Run a single ingestion cycle (cron mode)
+  ingestor daemon                       Serve HTTP endpoints and ingest every INGESTION_INTERVAL_SECS
+  ingestor sources discover <url> [--dry-run]
+                                        Find the feed behind a site and create an RSS source
+  ingestor sources backfill <source_id> [--max-pages N] [--since YYYY-MM-DD]
+                                        Walk an RSS source's archived/paged feed for older entries
+  ingestor submit <url> --source <source_id>
+                                        Fetch a page and store it as an item of a manual source";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Ingest,
+    Daemon,
+    DiscoverSource { url: String, dry_run: bool },
+    BackfillSource {
+        source_id: i32,
+        max_pages: Option<u32>,
+        since: Option<DateTime<Utc>>,
+    },
+    Submit { url: String, source_id: i32 },
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        match args.as_slice() {
+            [] | ["ingest"] => Ok(Command::Ingest),
+            ["daemon"] => Ok(Command::Daemon),
+            ["sources", "discover", url, flags @ ..] => Ok(Command::DiscoverSource {
+                url: url.to_string(),
+                dry_run: flags.contains(&"--dry-run"),
+            }),
+            ["sources", "backfill", source_id, flags @ ..] => {
+                let source_id = source_id
+                    .parse::<i32>()
+                    .map_err(|_| anyhow!("Invalid source id: {}", source_id))?;
+                let max_pages = flag_value(flags, "--max-pages")
+                    .map(|v| v.parse::<u32>().map_err(|_| anyhow!("--max-pages must be a number")))
+                    .transpose()?;
+                let since = flag_value(flags, "--since")
+                    .map(|v| parse_since(v).ok_or_else(|| anyhow!("--since must be YYYY-MM-DD or RFC 3339")))
+                    .transpose()?;
+                Ok(Command::BackfillSource { source_id, max_pages, since })
+            }
+            ["submit", url, flags @ ..] => {
+                let source_id = flag_value(flags, "--source")
+                    .ok_or_else(|| anyhow!("submit needs --source <source_id>"))?;
+                let source_id = source_id
+                    .parse::<i32>()
+                    .map_err(|_| anyhow!("Invalid source id: {}", source_id))?;
+                Ok(Command::Submit { url: url.to_string(), source_id })
+            }
+            _ => Err(anyhow!("Unrecognized arguments: {}\n\n{}", args.join(" "), USAGE)),
+        }
+    }
+
+    /// Whether the command needs a database connection
+    pub fn needs_database(&self) -> bool {
+        !matches!(self, Command::DiscoverSource { dry_run: true, .. })
+    }
+}
+
+/// Value following `name` in a flag list (`--max-pages 5`)
+fn flag_value<'a>(flags: &[&'a str], name: &str) -> Option<&'a str> {
+    flags
+        .iter()
+        .position(|flag| *flag == name)
+        .and_then(|i| flags.get(i + 1).copied())
+}
+
+/// `sources discover <url>`: list valid feeds and create a source from the first one
+pub async fn discover_source(pool: Option<&sqlx::PgPool>, url: &str) -> Result<()> {
+    let candidates = crate::discover::discover_feeds(url).await?;
+    if candidates.is_empty() {
+        return Err(anyhow!("No valid RSS/Atom feed found for {}", url));
+    }
+
+    for candidate in &candidates {
+        println!(
+            "{}  [{}] {} ({} entries)",
+            candidate.url,
+            candidate.medium.as_str(),
+            candidate.title.as_deref().unwrap_or("(untitled)"),
+            candidate.entry_count
+        );
+    }
+
+    if let Some(pool) = pool {
+        let source = crate::discover::create_source(pool, url, &candidates[0]).await?;
+        println!(
+            "Created source #{} '{}' ({}, {})",
+            source.id, source.name, source.source_type, source.medium
+        );
+    }
+
+    Ok(())
+}
+
+/// `sources backfill <source_id>`: flags override the source's `meta.backfill`
+pub async fn backfill_source(
+    pool: &sqlx::PgPool,
+    source_id: i32,
+    max_pages: Option<u32>,
+    since: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let source = crate::db::get_source(pool, source_id)
+        .await?
+        .ok_or_else(|| anyhow!("No source with id {}", source_id))?;
+    if source.source_type != "rss" {
+        return Err(anyhow!("Source #{} is a {} source; only RSS sources can be backfilled", source.id, source.source_type));
+    }
+
+    let mut options = BackfillOptions::from_meta(&source.meta);
+    if let Some(max_pages) = max_pages {
+        options.max_pages = max_pages;
+    }
+    if since.is_some() {
+        options.since = since;
+    }
+
+    let count = crate::sources::rss::backfill_rss_source(pool, &source, &options).await?;
+    println!("Backfilled source #{} '{}': {} items inserted/updated", source.id, source.name, count);
+
+    Ok(())
+}
+
+/// `submit <url> --source <source_id>`: store one page under a manual source
+pub async fn submit(pool: &sqlx::PgPool, url: &str, source_id: i32) -> Result<()> {
+    let source = crate::db::get_source(pool, source_id)
+        .await?
+        .ok_or_else(|| anyhow!("No source with id {}", source_id))?;
+
+    let submission = crate::sources::manual::submit_url(pool, &source, url).await?;
+    if submission.duplicate {
+        println!("Already stored: {} (item {})", submission.url, submission.item_id);
+    } else {
+        println!("Submitted '{}' to source #{} (item {})", submission.title, source.id, submission.item_id);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(Command::parse(&args(&[])).unwrap(), Command::Ingest);
+        assert_eq!(Command::parse(&args(&["daemon"])).unwrap(), Command::Daemon);
+        assert_eq!(
+            Command::parse(&args(&["sources", "discover", "https://example.com", "--dry-run"])).unwrap(),
+            Command::DiscoverSource {
+                url: "https://example.com".to_string(),
+                dry_run: true
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["sources", "backfill", "7", "--max-pages", "3", "--since", "2025-01-01"])).unwrap(),
+            Command::BackfillSource {
+                source_id: 7,
+                max_pages: Some(3),
+                since: parse_since("2025-01-01"),
+            }
+        );
+        assert_eq!(
+            Command::parse(&args(&["submit", "https://example.com/post", "--source", "4"])).unwrap(),
+            Command::Submit {
+                url: "https://example.com/post".to_string(),
+                source_id: 4
+            }
+        );
+        assert!(Command::parse(&args(&["submit", "https://example.com/post"])).is_err());
+        assert!(Command::parse(&args(&["sources", "backfill", "seven"])).is_err());
+        assert!(Command::parse(&args(&["bogus"])).is_err());
+    }
+}
@@ -5,6 +5,15 @@ pub struct Config {
     pub database_url: String,
     pub arxiv_api_url: String,
     pub ingestion_interval_secs: u64,
+    pub http_bind_addr: String,
+    pub websub_callback_base_url: Option<String>,
+    pub websub_secret: Option<String>,
+    pub submit_token: Option<String>,
+    pub github_token: Option<String>,
+    pub mastodon_access_token: Option<String>,
+    pub sec_user_agent: Option<String>,
+    pub twitter_bearer_token: Option<String>,
+    pub twitter_api_base_url: Option<String>,
 }
 
 impl Config {
@@ -20,10 +29,48 @@ impl Config {
             .parse::<u64>()
             .map_err(|_| anyhow!("INGESTION_INTERVAL_SECS must be a valid u64"))?;
 
+        let http_bind_addr = std::env::var("HTTP_BIND_ADDR")
+            .unwrap_or_else(|_| "0.0.0.0:8080".to_string());
+
+        // Public base URL hubs can reach; WebSub is disabled without it
+        let websub_callback_base_url = std::env::var("WEBSUB_CALLBACK_BASE_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
+
+        // Server key the per-source hub secrets are derived from; required with WebSub
+        let websub_secret = std::env::var("WEBSUB_SECRET")
+            .ok()
+            .filter(|secret| !secret.trim().is_empty());
+        if websub_callback_base_url.is_some() && websub_secret.is_none() {
+            return Err(anyhow!("WEBSUB_SECRET must be set when WEBSUB_CALLBACK_BASE_URL is"));
+        }
+
+        // Bearer token for POST /submit; the endpoint is disabled without it
+        let submit_token = std::env::var("SUBMIT_TOKEN")
+            .ok()
+            .filter(|token| !token.trim().is_empty());
+
+        // Credentials for API-backed sources; each is only required by its own source type
+        let optional = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        let github_token = optional("GITHUB_TOKEN");
+        let mastodon_access_token = optional("MASTODON_ACCESS_TOKEN");
+        let sec_user_agent = optional("SEC_USER_AGENT");
+        let twitter_bearer_token = optional("TWITTER_BEARER_TOKEN");
+        let twitter_api_base_url = optional("TWITTER_API_BASE_URL");
+
         Ok(Self {
             database_url,
             arxiv_api_url,
             ingestion_interval_secs,
+            http_bind_addr,
+            websub_callback_base_url,
+            websub_secret,
+            submit_token,
+            github_token,
+            mastodon_access_token,
+            sec_user_agent,
+            twitter_bearer_token,
+            twitter_api_base_url,
         })
     }
 }
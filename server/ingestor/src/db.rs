@@ -5,7 +5,8 @@ use sqlx::PgPool;
 use std::time::Duration;
 use uuid::Uuid;
 
-use crate::models::{Item, Source, ItemTopic, ItemLike};
+use crate::models::{Item, Source, Topic, ItemTopic, ItemLike};
+use crate::urls::{url_key, URL_KEY_SQL};
 
 // Legacy Paper struct - kept for backward compatibility during migration
 #[derive(Debug, Clone)]
@@ -96,6 +97,9 @@ pub async fn insert_or_update_paper(pool: &PgPool, paper: &Paper) -> Result<()>
 }
 
 // Source operations
+
+/// Insert a source, or update an existing one with the same name and type.
+/// `meta` keys are merged into the stored meta so runtime state (`feed`, `websub`, ...) survives.
 pub async fn get_or_create_source(
     pool: &PgPool,
     name: &str,
@@ -108,7 +112,7 @@ pub async fn get_or_create_source(
         "INSERT INTO sources (name, type, medium, ingest_url, active, meta)
          VALUES ($1, $2, $3, $4, true, $5)
          ON CONFLICT (name, type) DO UPDATE
-         SET medium = $3, ingest_url = $4, meta = $5
+         SET medium = $3, ingest_url = $4, meta = COALESCE(sources.meta, '{}'::JSONB) || $5, updated_at = NOW()
          RETURNING *"
     )
     .bind(name)
@@ -132,18 +136,213 @@ pub async fn get_active_sources(pool: &PgPool) -> Result<Vec<Source>> {
     Ok(sources)
 }
 
+pub async fn get_source(pool: &PgPool, source_id: i32) -> Result<Option<Source>> {
+    let source = sqlx::query_as::<_, Source>(
+        "SELECT * FROM sources WHERE id = $1"
+    )
+    .bind(source_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(source)
+}
+
+/// Replace one top-level key of a source's `meta` (e.g. `feed`, `websub`)
+pub async fn set_source_meta_key(pool: &PgPool, source_id: i32, key: &str, value: &serde_json::Value) -> Result<()> {
+    sqlx::query(
+        "UPDATE sources
+         SET meta = COALESCE(meta, '{}'::JSONB) || jsonb_build_object($2::TEXT, $3::JSONB), updated_at = NOW()
+         WHERE id = $1"
+    )
+    .bind(source_id)
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 // Item operations
+
+/// Find an existing item for the same source, matching on external_id first and url (by `url_key`) second
+pub async fn find_existing_item_id(
+    pool: &PgPool,
+    source_id: i32,
+    external_id: Option<&str>,
+    url: &str,
+) -> Result<Option<Uuid>> {
+    if let Some(external_id) = external_id {
+        let by_external_id = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM items WHERE source_id = $1 AND external_id = $2"
+        )
+        .bind(source_id)
+        .bind(external_id)
+        .fetch_optional(pool)
+        .await?;
+
+        if by_external_id.is_some() {
+            return Ok(by_external_id);
+        }
+    }
+
+    let by_url = sqlx::query_scalar::<_, Uuid>(&format!(
+        "SELECT id FROM items WHERE source_id = $1 AND {} = $2 ORDER BY created_at LIMIT 1",
+        URL_KEY_SQL
+    ))
+    .bind(source_id)
+    .bind(url_key(url))
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(by_url)
+}
+
+/// Which of `external_ids` the source has already stored
+pub async fn find_existing_external_ids(
+    pool: &PgPool,
+    source_id: i32,
+    external_ids: &[String],
+) -> Result<std::collections::HashSet<String>> {
+    if external_ids.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let found = sqlx::query_scalar::<_, String>(
+        "SELECT external_id FROM items WHERE source_id = $1 AND external_id = ANY($2)"
+    )
+    .bind(source_id)
+    .bind(external_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(found.into_iter().collect())
+}
+
+/// Which of `urls` the source has already stored, returned as their `url_key`s
+pub async fn find_existing_url_keys(
+    pool: &PgPool,
+    source_id: i32,
+    urls: &[String],
+) -> Result<std::collections::HashSet<String>> {
+    if urls.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let keys: Vec<String> = urls.iter().map(|url| url_key(url)).collect();
+    let found = sqlx::query_scalar::<_, String>(&format!(
+        "SELECT DISTINCT {key} FROM items WHERE source_id = $1 AND {key} = ANY($2)",
+        key = URL_KEY_SQL
+    ))
+    .bind(source_id)
+    .bind(&keys)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(found.into_iter().collect())
+}
+
+pub async fn source_has_items(pool: &PgPool, source_id: i32) -> Result<bool> {
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM items WHERE source_id = $1)"
+    )
+    .bind(source_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists)
+}
+
+/// Insert an item or update the row it matches: the source's row with the same external_id,
+/// else the one holding the same link under any http(s)/`www.` variant (`url_key`).
+/// New items are upserted on (source_id, external_id) or (source_id, url), so a row written
+/// concurrently is updated in place instead of raising a unique violation.
+/// `raw_metadata` is merged key by key, so keys other sources attached to the row
+/// (`hackernews`, `huggingface`, `openreview`) survive a re-ingestion.
 pub async fn insert_or_update_item(pool: &PgPool, item: &Item) -> Result<Uuid> {
-    let result = sqlx::query_scalar::<_, Uuid>(
-        "INSERT INTO items (id, source_id, source_type, title, url, summary, body, published_at, raw_metadata, created_at, updated_at)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-         ON CONFLICT (source_id, url) DO UPDATE
-         SET title = $4, summary = $6, body = $7, published_at = $8, raw_metadata = $9, updated_at = $11
+    if let Some(existing) = find_existing_item_id(pool, item.source_id, item.external_id.as_deref(), &item.url).await? {
+        if let Some(item_id) = update_item(pool, existing, item).await? {
+            return Ok(item_id);
+        }
+    }
+
+    if item.external_id.is_some() {
+        // The url only moves when no other row of the source already uses it
+        let by_external_id = upsert_item(pool, item,
+            "ON CONFLICT (source_id, external_id) DO UPDATE
+             SET title = EXCLUDED.title,
+                 url = CASE WHEN EXISTS (
+                         SELECT 1 FROM items other
+                         WHERE other.source_id = EXCLUDED.source_id AND other.url = EXCLUDED.url AND other.id <> items.id
+                       ) THEN items.url ELSE EXCLUDED.url END,
+                 summary = EXCLUDED.summary, body = EXCLUDED.body, published_at = EXCLUDED.published_at,
+                 raw_metadata = COALESCE(items.raw_metadata, '{}'::JSONB) || EXCLUDED.raw_metadata, updated_at = EXCLUDED.updated_at"
+        ).await;
+
+        match by_external_id {
+            Err(sqlx::Error::Database(e)) if e.constraint() == Some(URL_UNIQUE_INDEX) => {
+                // A new external_id for a url stored before; adopt the id if the row has none
+            }
+            other => return other.map_err(Into::into),
+        }
+    }
+
+    // Keep a previously stored external_id if the source stopped sending one
+    let item_id = upsert_item(pool, item,
+        "ON CONFLICT (source_id, url) DO UPDATE
+         SET external_id = COALESCE(items.external_id, EXCLUDED.external_id), title = EXCLUDED.title,
+             summary = EXCLUDED.summary, body = EXCLUDED.body, published_at = EXCLUDED.published_at,
+             raw_metadata = COALESCE(items.raw_metadata, '{}'::JSONB) || EXCLUDED.raw_metadata, updated_at = EXCLUDED.updated_at"
+    ).await?;
+
+    Ok(item_id)
+}
+
+/// Unique index on items(source_id, url)
+const URL_UNIQUE_INDEX: &str = "idx_items_dedup";
+
+/// Update a known row; a previously stored external_id is kept, and the url only moves
+/// when no other row of the source already uses it. `None` if the row is gone.
+async fn update_item(pool: &PgPool, item_id: Uuid, item: &Item) -> Result<Option<Uuid>> {
+    let updated = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE items
+         SET external_id = COALESCE(items.external_id, $2), title = $3,
+             url = CASE WHEN EXISTS (
+                     SELECT 1 FROM items other
+                     WHERE other.source_id = items.source_id AND other.url = $4 AND other.id <> items.id
+                   ) THEN items.url ELSE $4 END,
+             summary = $5, body = $6, published_at = $7,
+             raw_metadata = COALESCE(items.raw_metadata, '{}'::JSONB) || $8, updated_at = $9
+         WHERE id = $1
          RETURNING id"
     )
+    .bind(item_id)
+    .bind(&item.external_id)
+    .bind(&item.title)
+    .bind(&item.url)
+    .bind(&item.summary)
+    .bind(&item.body)
+    .bind(item.published_at)
+    .bind(&item.raw_metadata)
+    .bind(item.updated_at)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(updated)
+}
+
+async fn upsert_item(pool: &PgPool, item: &Item, on_conflict: &str) -> std::result::Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar::<_, Uuid>(&format!(
+        "INSERT INTO items (id, source_id, source_type, external_id, title, url, summary, body, published_at, raw_metadata, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+         {}
+         RETURNING id",
+        on_conflict
+    ))
     .bind(item.id)
     .bind(item.source_id)
     .bind(&item.source_type)
+    .bind(&item.external_id)
     .bind(&item.title)
     .bind(&item.url)
     .bind(&item.summary)
@@ -153,9 +352,137 @@ pub async fn insert_or_update_item(pool: &PgPool, item: &Item) -> Result<Uuid> {
     .bind(item.created_at)
     .bind(item.updated_at)
     .fetch_one(pool)
+    .await
+}
+
+/// An item from a different source with the same url (compared by `url_key`), oldest first
+pub async fn find_item_by_url_from_other_source(pool: &PgPool, source_id: i32, url: &str) -> Result<Option<Uuid>> {
+    let item_id = sqlx::query_scalar::<_, Uuid>(&format!(
+        "SELECT id FROM items WHERE {} = $1 AND source_id <> $2 ORDER BY created_at LIMIT 1",
+        URL_KEY_SQL
+    ))
+    .bind(url_key(url))
+    .bind(source_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(item_id)
+}
+
+/// The oldest item from a different source recording this (version-less) arXiv id
+pub async fn find_arxiv_item(pool: &PgPool, source_id: i32, arxiv_id: &str) -> Result<Option<Uuid>> {
+    let item_id = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM items WHERE raw_metadata->>'arxiv_id' = $1 AND source_id <> $2 ORDER BY created_at LIMIT 1"
+    )
+    .bind(arxiv_id)
+    .bind(source_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(item_id)
+}
+
+/// Title lowercased with whitespace collapsed; backed by the `idx_items_arxiv_normalized_title`
+/// expression index (for rows with an `arxiv_id`), so it must stay identical to the migration
+const NORMALIZED_TITLE_SQL: &str = "lower(btrim(regexp_replace(title, '\\s+', ' ', 'g')))";
+
+/// Items recording an arXiv id whose title, lowercased with whitespace collapsed, is one of `titles`.
+/// Returns (item id, normalised title, arXiv id, url).
+pub async fn find_arxiv_items_by_title(pool: &PgPool, titles: &[String]) -> Result<Vec<(Uuid, String, String, String)>> {
+    let rows = sqlx::query_as::<_, (Uuid, String, String, String)>(&format!(
+        "SELECT id, {title} AS normalized_title, raw_metadata->>'arxiv_id', url
+         FROM items
+         WHERE raw_metadata ? 'arxiv_id'
+           AND {title} = ANY($1)
+         ORDER BY created_at",
+        title = NORMALIZED_TITLE_SQL
+    ))
+    .bind(titles)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Replace one top-level key of an item's `raw_metadata` (e.g. `hackernews`)
+pub async fn set_item_metadata_key(pool: &PgPool, item_id: Uuid, key: &str, value: &serde_json::Value) -> Result<()> {
+    sqlx::query(
+        "UPDATE items
+         SET raw_metadata = COALESCE(raw_metadata, '{}'::JSONB) || jsonb_build_object($2::TEXT, $3::JSONB), updated_at = NOW()
+         WHERE id = $1"
+    )
+    .bind(item_id)
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Body and word count of an item whose full article text was fetched in an earlier cycle
+pub async fn get_full_text(
+    pool: &PgPool,
+    source_id: i32,
+    external_id: Option<&str>,
+    url: &str,
+) -> Result<Option<StoredFullText>> {
+    let Some(item_id) = find_existing_item_id(pool, source_id, external_id, url).await? else {
+        return Ok(None);
+    };
+
+    let row = sqlx::query_as::<_, (Option<String>, Option<bool>, Option<i64>, Option<String>)>(
+        "SELECT body, (raw_metadata->>'full_text')::BOOLEAN, (raw_metadata->>'word_count')::BIGINT,
+                raw_metadata->>'full_text_failed_at'
+         FROM items WHERE id = $1"
+    )
+    .bind(item_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(match row {
+        Some((Some(body), Some(true), word_count, _)) => {
+            Some(StoredFullText::Article(body, word_count.unwrap_or(0).max(0) as usize))
+        }
+        Some((_, _, _, Some(failed_at))) => DateTime::parse_from_rfc3339(&failed_at)
+            .ok()
+            .map(|at| StoredFullText::FailedAt(at.with_timezone(&Utc))),
+        _ => None,
+    })
+}
+
+/// What an earlier cycle stored for an item's full text
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoredFullText {
+    /// Extracted article body and its word count
+    Article(String, usize),
+    /// Fetching or extraction failed (or found less than the feed had) at this time
+    FailedAt(DateTime<Utc>),
+}
+
+/// Body and `raw_metadata.transcript` of a video whose captions were fetched in an earlier cycle
+pub async fn get_transcript(
+    pool: &PgPool,
+    source_id: i32,
+    external_id: Option<&str>,
+    url: &str,
+) -> Result<Option<(String, serde_json::Value)>> {
+    let Some(item_id) = find_existing_item_id(pool, source_id, external_id, url).await? else {
+        return Ok(None);
+    };
+
+    let row = sqlx::query_as::<_, (Option<String>, Option<serde_json::Value>)>(
+        "SELECT body, raw_metadata->'transcript' FROM items
+         WHERE id = $1 AND raw_metadata ? 'transcript'"
+    )
+    .bind(item_id)
+    .fetch_optional(pool)
     .await?;
 
-    Ok(result)
+    Ok(match row {
+        Some((Some(body), Some(transcript))) => Some((body, transcript)),
+        _ => None,
+    })
 }
 
 pub async fn get_items_by_source(pool: &PgPool, source_id: i32, limit: i64) -> Result<Vec<Item>> {
@@ -181,6 +508,18 @@ pub async fn get_latest_items(pool: &PgPool, limit: i64) -> Result<Vec<Item>> {
     Ok(items)
 }
 
+// Topics
+
+pub async fn get_active_topics(pool: &PgPool) -> Result<Vec<Topic>> {
+    let topics = sqlx::query_as::<_, Topic>(
+        "SELECT * FROM topics WHERE active = true ORDER BY position, name"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(topics)
+}
+
 // Item topics
 pub async fn add_item_topic(pool: &PgPool, item_id: Uuid, topic: &str) -> Result<()> {
     sqlx::query(
@@ -261,3 +600,108 @@ pub async fn get_item_likes(pool: &PgPool, item_id: Uuid) -> Result<Vec<ItemLike
 
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pool on a migrated scratch database from `TEST_DATABASE_URL`; tests needing Postgres
+    /// are skipped without it
+    async fn test_pool() -> Option<PgPool> {
+        let url = std::env::var("TEST_DATABASE_URL").ok().filter(|url| !url.trim().is_empty())?;
+        let pool = create_pool(&url).await.expect("TEST_DATABASE_URL is not reachable");
+        sqlx::migrate!("../migrations").run(&pool).await.expect("Failed to run migrations");
+        Some(pool)
+    }
+
+    fn item(source_id: i32, external_id: Option<&str>, url: &str, raw_metadata: serde_json::Value) -> Item {
+        Item {
+            id: Uuid::new_v4(),
+            source_id,
+            source_type: "paper".to_string(),
+            external_id: external_id.map(str::to_string),
+            title: "Attention Is All You Need".to_string(),
+            url: url.to_string(),
+            summary: None,
+            body: None,
+            published_at: Utc::now(),
+            raw_metadata,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reingestion_keeps_attached_metadata() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let source_id = (Uuid::new_v4().as_u128() % 1_000_000_000) as i32 + 1_000_000_000;
+
+        for external_id in [Some("1706.03762"), None] {
+            let url = format!("https://arxiv.org/abs/1706.03762?via={}", external_id.is_some());
+            let stored = item(source_id, external_id, &url, serde_json::json!({ "arxiv_id": "1706.03762", "version": 1 }));
+            let item_id = insert_or_update_item(&pool, &stored).await.unwrap();
+            set_item_metadata_key(&pool, item_id, "hackernews", &serde_json::json!({ "points": 120 })).await.unwrap();
+
+            let again = item(source_id, external_id, &url, serde_json::json!({ "arxiv_id": "1706.03762", "version": 2 }));
+            assert_eq!(insert_or_update_item(&pool, &again).await.unwrap(), item_id);
+
+            let metadata = sqlx::query_scalar::<_, serde_json::Value>("SELECT raw_metadata FROM items WHERE id = $1")
+                .bind(item_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(metadata["hackernews"]["points"], 120);
+            assert_eq!(metadata["version"], 2);
+        }
+
+        sqlx::query("DELETE FROM items WHERE source_id = $1").bind(source_id).execute(&pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scheme_and_www_variants_update_one_row() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let source_id = (Uuid::new_v4().as_u128() % 1_000_000_000) as i32 + 1_000_000_000;
+
+        let first = insert_or_update_item(&pool, &item(source_id, None, "http://www.example.com/a", serde_json::json!({})))
+            .await
+            .unwrap();
+        let second = insert_or_update_item(&pool, &item(source_id, None, "https://example.com/a", serde_json::json!({})))
+            .await
+            .unwrap();
+        let with_id = insert_or_update_item(&pool, &item(source_id, Some("a"), "https://www.example.com/a", serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first, with_id);
+
+        let rows = sqlx::query_as::<_, (String, Option<String>)>("SELECT url, external_id FROM items WHERE source_id = $1")
+            .bind(source_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![("https://www.example.com/a".to_string(), Some("a".to_string()))]);
+
+        sqlx::query("DELETE FROM items WHERE source_id = $1").bind(source_id).execute(&pool).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_existing_url_keys() {
+        let Some(pool) = test_pool().await else {
+            return;
+        };
+        let source_id = (Uuid::new_v4().as_u128() % 1_000_000_000) as i32 + 1_000_000_000;
+        insert_or_update_item(&pool, &item(source_id, None, "https://www.example.com/a", serde_json::json!({})))
+            .await
+            .unwrap();
+
+        let urls = ["http://example.com/a".to_string(), "https://example.com/b".to_string()];
+        let stored = find_existing_url_keys(&pool, source_id, &urls).await.unwrap();
+        assert_eq!(stored, [url_key(&urls[0])].into_iter().collect());
+
+        sqlx::query("DELETE FROM items WHERE source_id = $1").bind(source_id).execute(&pool).await.unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+//! Feed autodiscovery: find the RSS/Atom feed(s) behind a site URL
+//! Used by `ingestor sources discover <url>`
+
+use anyhow::{anyhow, Result};
+use scraper::{Html, Selector};
+use sqlx::PgPool;
+use url::Url;
+
+use crate::models::{Medium, Source, SourceType};
+
+/// Paths tried when the page doesn't advertise a feed
+const COMMON_FEED_PATHS: &[&str] = &[
+    "/feed",
+    "/rss",
+    "/feed.xml",
+    "/rss.xml",
+    "/atom.xml",
+    "/index.xml",
+    "/feed/atom",
+];
+
+/// Hosts/generators that are newsletters rather than blogs
+const NEWSLETTER_HINTS: &[&str] = &["substack", "buttondown", "beehiiv", "ghost", "mailchimp"];
+
+/// A validated feed found for a site
+#[derive(Debug, Clone)]
+pub struct FeedCandidate {
+    pub url: String,
+    pub title: Option<String>,
+    pub medium: Medium,
+    pub entry_count: usize,
+}
+
+/// Find feeds for a site: advertised `<link rel="alternate">` feeds first, then common paths.
+/// Every candidate is fetched and parsed; only valid feeds are returned.
+pub async fn discover_feeds(site_url: &str) -> Result<Vec<FeedCandidate>> {
+    let base = Url::parse(site_url)?;
+    let fetcher = crate::http::shared();
+
+    let mut advertised = Vec::new();
+    match fetcher.get_text(base.as_str()).await {
+        Ok(page) => {
+            // The URL may already be a feed
+            if let Some(candidate) = validate_feed(base.as_str(), &page) {
+                return Ok(vec![candidate]);
+            }
+            advertised.extend(find_feed_links(&page, &base));
+        }
+        Err(e) => log::warn!("Failed to fetch {}: {}", site_url, e),
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut feeds = validate_candidates(advertised, &mut seen).await;
+
+    // Probe common paths only when the page advertised no valid feed
+    if feeds.is_empty() {
+        let probes = COMMON_FEED_PATHS
+            .iter()
+            .filter_map(|path| base.join(path).ok())
+            .map(|url| url.to_string())
+            .collect();
+        feeds = validate_candidates(probes, &mut seen).await;
+    }
+
+    Ok(feeds)
+}
+
+/// Fetch and validate each URL not tried before
+async fn validate_candidates(urls: Vec<String>, seen: &mut std::collections::HashSet<String>) -> Vec<FeedCandidate> {
+    let fetcher = crate::http::shared();
+    let mut feeds = Vec::new();
+    for url in urls {
+        if !seen.insert(url.clone()) {
+            continue;
+        }
+
+        match fetcher.get_text(&url).await {
+            Ok(body) => {
+                if let Some(candidate) = validate_feed(&url, &body) {
+                    feeds.push(candidate);
+                }
+            }
+            Err(e) => log::debug!("Feed candidate {} failed: {}", url, e),
+        }
+    }
+    feeds
+}
+
+/// `<link rel="alternate" type="application/rss+xml|atom+xml" href="...">` links, resolved against the page
+pub fn find_feed_links(html: &str, base: &Url) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let Ok(selector) = Selector::parse(r#"link[rel~="alternate"][href]"#) else {
+        return Vec::new();
+    };
+
+    document
+        .select(&selector)
+        .filter(|el| {
+            el.value()
+                .attr("type")
+                .map(|t| {
+                    let t = t.to_lowercase();
+                    t.contains("rss+xml") || t.contains("atom+xml") || t.contains("feed+json")
+                })
+                .unwrap_or(false)
+        })
+        .filter_map(|el| el.value().attr("href"))
+        .filter_map(|href| base.join(href.trim()).ok())
+        .map(|url| url.to_string())
+        .collect()
+}
+
+/// Parse a fetched document with feed-rs and describe it if it is a feed
+fn validate_feed(url: &str, body: &str) -> Option<FeedCandidate> {
+    let feed = feed_rs::parser::parse(body.as_bytes()).ok()?;
+
+    let generator = feed
+        .generator
+        .as_ref()
+        .map(|g| g.content.to_lowercase())
+        .unwrap_or_default();
+    let lower_url = url.to_lowercase();
+    let medium = if NEWSLETTER_HINTS
+        .iter()
+        .any(|hint| lower_url.contains(hint) || generator.contains(hint))
+    {
+        Medium::Newsletter
+    } else {
+        Medium::Blog
+    };
+
+    Some(FeedCandidate {
+        url: url.to_string(),
+        title: feed.title.map(|t| t.content.trim().to_string()),
+        medium,
+        entry_count: feed.entries.len(),
+    })
+}
+
+/// Create (or update) an RSS source from a discovered feed
+pub async fn create_source(pool: &PgPool, site_url: &str, candidate: &FeedCandidate) -> Result<Source> {
+    let name = candidate
+        .title
+        .clone()
+        .filter(|t| !t.is_empty())
+        .or_else(|| Url::parse(site_url).ok().and_then(|u| u.host_str().map(str::to_string)))
+        .ok_or_else(|| anyhow!("Could not derive a source name for {}", site_url))?;
+
+    let meta = serde_json::json!({
+        "site_url": site_url,
+        "discovered": true,
+    });
+
+    crate::db::get_or_create_source(
+        pool,
+        &name,
+        SourceType::Rss.as_str(),
+        candidate.medium.as_str(),
+        Some(&candidate.url),
+        meta,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_feed_links() {
+        let base = Url::parse("https://example.com/blog/").unwrap();
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="/style.css">
+            <link rel="alternate" type="application/rss+xml" title="RSS" href="/feed.xml">
+            <link rel="alternate" type="application/atom+xml" href="https://example.com/atom">
+            <link rel="alternate" hreflang="fr" href="/fr/">
+        </head></html>"#;
+
+        assert_eq!(
+            find_feed_links(html, &base),
+            vec!["https://example.com/feed.xml", "https://example.com/atom"]
+        );
+    }
+
+    #[test]
+    fn test_validate_feed_detects_newsletter() {
+        let rss = r#"<?xml version="1.0"?><rss version="2.0"><channel>
+            <title>Import AI</title><link>https://importai.substack.com</link>
+            <generator>Substack</generator>
+            <item><title>Issue 1</title><link>https://importai.substack.com/p/1</link></item>
+        </channel></rss>"#;
+
+        let candidate = validate_feed("https://importai.substack.com/feed", rss).unwrap();
+        assert_eq!(candidate.title.as_deref(), Some("Import AI"));
+        assert_eq!(candidate.medium, Medium::Newsletter);
+        assert_eq!(candidate.entry_count, 1);
+    }
+
+    #[test]
+    fn test_validate_feed_rejects_html() {
+        assert!(validate_feed("https://example.com", "<html><body>Hi</body></html>").is_none());
+    }
+}
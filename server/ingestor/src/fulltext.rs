@@ -0,0 +1,263 @@
+//! Full-article fetching for feeds that only publish a teaser
+//! Enabled per source with `meta.fetch_full_text = true`
+
+use crate::html::{html_to_text, sanitize_html, truncate_chars};
+use crate::db::StoredFullText;
+use crate::models::{Item, Source};
+use chrono::{DateTime, Utc};
+use scraper::{ElementRef, Html, Selector};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// How long to wait before retrying a page whose full text couldn't be extracted
+const RETRY_FAILED_AFTER: chrono::Duration = chrono::Duration::days(7);
+
+/// Words per minute used for the reading time estimate
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Elements considered as the article container
+const CANDIDATE_SELECTOR: &str = "article, main, section, div, td";
+
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "story", "text", "blog",
+];
+
+const NEGATIVE_HINTS: &[&str] = &[
+    "comment", "footer", "footnote", "header", "menu", "nav", "related", "share", "sidebar",
+    "social", "sponsor", "subscribe", "promo", "advert", "banner", "widget", "popup", "modal",
+];
+
+/// Main content of an article page
+#[derive(Debug, Clone)]
+pub struct ExtractedArticle {
+    pub html: String,
+    pub text: String,
+    pub word_count: usize,
+}
+
+pub fn reading_time_minutes(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE).max(1)
+}
+
+pub fn is_enabled(source: &Source) -> bool {
+    source
+        .meta
+        .get("fetch_full_text")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Replace teaser bodies with the full article for each item.
+/// Items that were already fetched in a previous cycle reuse the stored body.
+pub async fn enrich_items(pool: &PgPool, items: &mut [Item]) {
+    let fetcher = crate::http::shared();
+
+    for item in items.iter_mut() {
+        match crate::db::get_full_text(pool, item.source_id, item.external_id.as_deref(), &item.url).await {
+            Ok(Some(StoredFullText::Article(body, word_count))) => {
+                apply_full_text(item, body, word_count);
+                continue;
+            }
+            Ok(Some(StoredFullText::FailedAt(failed_at))) if Utc::now() - failed_at < RETRY_FAILED_AFTER => {
+                mark_failed(item, failed_at);
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to look up stored full text for {}: {}", item.url, e),
+        }
+
+        let page = match fetcher.get_text(&item.url).await {
+            Ok(page) => page,
+            Err(e) => {
+                log::warn!("Failed to fetch full text for {}: {}", item.url, e);
+                mark_failed(item, Utc::now());
+                continue;
+            }
+        };
+
+        let Some(article) = extract_main_content(&page) else {
+            log::debug!("No article content found at {}", item.url);
+            mark_failed(item, Utc::now());
+            continue;
+        };
+
+        // Keep the feed's content when it's already longer than what we extracted
+        let current_len = item.body.as_deref().map(|b| html_to_text(b).len()).unwrap_or(0);
+        if article.text.len() <= current_len {
+            mark_failed(item, Utc::now());
+            continue;
+        }
+
+        apply_article(item, &article);
+    }
+}
+
+/// Use an extracted article as the item's body
+pub fn apply_article(item: &mut Item, article: &ExtractedArticle) {
+    apply_full_text(item, truncate_html(&article.html), article.word_count);
+}
+
+fn truncate_html(html: &str) -> String {
+    // Full articles get a larger limit than feed bodies; re-sanitize so cut tags are closed
+    sanitize_html(&truncate_chars(html, 50000))
+}
+
+/// Remember a failed attempt so the page isn't refetched every cycle
+fn mark_failed(item: &mut Item, at: DateTime<Utc>) {
+    if let Some(obj) = item.raw_metadata.as_object_mut() {
+        obj.insert("full_text_failed_at".to_string(), serde_json::json!(at.to_rfc3339()));
+    }
+}
+
+fn apply_full_text(item: &mut Item, body: String, word_count: usize) {
+    let reading_time = reading_time_minutes(word_count);
+    item.body = Some(body);
+    if let Some(obj) = item.raw_metadata.as_object_mut() {
+        obj.insert("full_text".to_string(), serde_json::json!(true));
+        obj.insert("word_count".to_string(), serde_json::json!(word_count));
+        obj.insert("reading_time_minutes".to_string(), serde_json::json!(reading_time));
+    }
+}
+
+/// Readability-style extraction: each paragraph scores its parent container in full and its
+/// grandparent by half, so a wrapper around the article (and the page chrome) doesn't
+/// outscore the element that actually holds the paragraphs. Scores are adjusted by
+/// class/id hints and link density, and the best container is returned as sanitized HTML
+pub fn extract_main_content(page: &str) -> Option<ExtractedArticle> {
+    let document = Html::parse_document(page);
+    let candidates = Selector::parse(CANDIDATE_SELECTOR).ok()?;
+    let paragraphs = Selector::parse("p, pre, blockquote, li").ok()?;
+
+    // Candidates in document order, so ties go to the first one like before
+    let mut scored: Vec<(f64, ElementRef)> = Vec::new();
+    let mut index = HashMap::new();
+    for p in document.select(&paragraphs) {
+        let text: String = p.text().collect();
+        let len = text.trim().len();
+        if len < 25 {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (len as f64 / 100.0).min(3.0);
+
+        let parent = p.parent().and_then(ElementRef::wrap);
+        let grandparent = parent.and_then(|parent| parent.parent()).and_then(ElementRef::wrap);
+        for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            let Some(ancestor) = ancestor.filter(|element| candidates.matches(element)) else {
+                continue;
+            };
+            let slot = *index.entry(ancestor.id()).or_insert_with(|| {
+                scored.push((0.0, ancestor));
+                scored.len() - 1
+            });
+            scored[slot].0 += score * share;
+        }
+    }
+
+    let mut best: Option<(f64, ElementRef)> = None;
+    for (score, candidate) in scored {
+        let hint = class_weight(candidate);
+        if hint < 0.0 {
+            continue;
+        }
+
+        // Penalize link-heavy containers (navigation, tag clouds)
+        let score = (score + hint) * (1.0 - link_density(candidate));
+
+        if best.as_ref().map(|(s, _)| score > *s).unwrap_or(true) {
+            best = Some((score, candidate));
+        }
+    }
+
+    let (_, element) = best?;
+    let html = sanitize_html(&element.html());
+    let text = html_to_text(&html);
+    let word_count = text.split_whitespace().count();
+    if word_count == 0 {
+        return None;
+    }
+
+    Some(ExtractedArticle {
+        html,
+        text,
+        word_count,
+    })
+}
+
+fn class_weight(element: ElementRef) -> f64 {
+    let value = element.value();
+    let mut weight = 0.0;
+    if value.name() == "article" || value.name() == "main" {
+        weight += 10.0;
+    }
+
+    let names = value
+        .classes()
+        .chain(value.id())
+        .map(|n| n.to_lowercase())
+        .collect::<Vec<_>>();
+    for name in &names {
+        if NEGATIVE_HINTS.iter().any(|h| name.contains(h)) {
+            weight -= 25.0;
+        }
+        if POSITIVE_HINTS.iter().any(|h| name.contains(h)) {
+            weight += 25.0;
+        }
+    }
+    weight
+}
+
+fn link_density(element: ElementRef) -> f64 {
+    let links = match Selector::parse("a") {
+        Ok(selector) => selector,
+        Err(_) => return 0.0,
+    };
+    let total = element.text().map(str::len).sum::<usize>();
+    if total == 0 {
+        return 1.0;
+    }
+    let linked = element
+        .select(&links)
+        .flat_map(|a| a.text())
+        .map(str::len)
+        .sum::<usize>();
+    (linked as f64 / total as f64).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_main_content_prefers_article_body() {
+        let page = r#"<html><body>
+            <nav class="menu"><a href="/">Home</a> <a href="/about">About</a></nav>
+            <div class="post-content">
+              <p>Large language models keep getting better, cheaper, and easier to run locally.</p>
+              <p>In this post we look at quantization, serving, and what it means for builders.</p>
+            </div>
+            <div class="comments"><p>Great post, thanks for sharing this with everyone here!</p></div>
+        </body></html>"#;
+
+        let article = extract_main_content(page).unwrap();
+        assert!(article.text.contains("quantization"));
+        assert!(!article.text.contains("Great post"));
+        assert!(!article.text.contains("About"));
+        assert_eq!(reading_time_minutes(article.word_count), 1);
+    }
+
+    #[test]
+    fn test_extract_main_content_prefers_paragraph_container_over_wrapper() {
+        let page = r#"<html><body><div>
+            <div>
+              <p>Large language models keep getting better, cheaper, and easier to run locally.</p>
+              <p>In this post we look at quantization, serving, and what it means for builders.</p>
+              <p>We close with a few benchmarks, caveats, and pointers to further reading.</p>
+            </div>
+            <div><p>Sign up for the weekly newsletter, delivered every Friday morning.</p></div>
+        </div></body></html>"#;
+
+        let article = extract_main_content(page).unwrap();
+        assert!(article.text.contains("benchmarks"));
+        assert!(!article.text.contains("newsletter"));
+    }
+}
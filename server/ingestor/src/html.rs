@@ -0,0 +1,145 @@
+//! HTML cleanup for feed content
+//! `html_to_text` produces plain text for summaries and topic extraction,
+//! `sanitize_html` produces safe HTML for bodies
+
+use scraper::{ElementRef, Html, Node};
+
+/// Elements whose content is never readable text
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "form", "button", "head",
+];
+
+/// Elements that start a new line in the text output
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "br", "li", "ul", "ol", "h1", "h2", "h3", "h4", "h5", "h6", "blockquote", "pre",
+    "table", "tr", "section", "article", "header", "footer", "figure", "figcaption", "hr",
+];
+
+/// Class fragments used by Substack/Ghost/WordPress for subscribe and share widgets
+const BOILERPLATE_CLASSES: &[&str] = &[
+    "subscription-widget",
+    "subscribe-widget",
+    "button-wrapper",
+    "share-dialog",
+    "footnote-anchor",
+    "kg-signup-card",
+    "sharedaddy",
+];
+
+/// Whole lines that are newsletter boilerplate rather than content
+const BOILERPLATE_LINES: &[&str] = &[
+    "subscribe now",
+    "share",
+    "leave a comment",
+    "give a gift subscription",
+    "upgrade to paid",
+];
+
+/// Convert HTML into plain text: tags removed, entities decoded, whitespace collapsed
+pub fn html_to_text(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut out = String::new();
+    collect_text(fragment.root_element(), &mut out);
+
+    out.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty() && !is_boilerplate_line(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn collect_text(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(el) => {
+                let name = el.name();
+                if SKIPPED_TAGS.contains(&name) || is_boilerplate_element(el) {
+                    continue;
+                }
+                let is_block = BLOCK_TAGS.contains(&name);
+                if is_block {
+                    out.push('\n');
+                }
+                if let Some(child_ref) = ElementRef::wrap(child) {
+                    collect_text(child_ref, out);
+                }
+                if is_block {
+                    out.push('\n');
+                } else if name == "img" {
+                    // Keep alt text, but never the tag itself
+                    if let Some(alt) = el.attr("alt") {
+                        out.push_str(alt);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_boilerplate_element(el: &scraper::node::Element) -> bool {
+    el.classes()
+        .any(|class| BOILERPLATE_CLASSES.iter().any(|b| class.contains(b)))
+}
+
+fn is_boilerplate_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    BOILERPLATE_LINES.contains(&lower.as_str())
+        || (lower.starts_with("thanks for reading") && lower.contains("subscribe"))
+}
+
+/// Sanitize HTML for storage in `items.body`: keeps formatting and links,
+/// drops scripts, styles, event handlers and tracking attributes
+pub fn sanitize_html(html: &str) -> String {
+    ammonia::Builder::default()
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .clean(html)
+        .to_string()
+}
+
+/// Truncate to at most `max_chars` characters, appending "..." when something was cut
+pub fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((byte_idx, _)) => format!("{}...", &text[..byte_idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text_strips_tags_and_entities() {
+        let text = html_to_text("<p>Hello &amp; <b>welcome</b></p><p>Second&nbsp;para</p>");
+        assert_eq!(text, "Hello & welcome\nSecond para");
+    }
+
+    #[test]
+    fn test_html_to_text_ignores_markup_attributes() {
+        let text = html_to_text(r#"<p>Intro</p><img src="image.png"><script>var image = 1;</script>"#);
+        assert_eq!(text, "Intro");
+    }
+
+    #[test]
+    fn test_html_to_text_drops_substack_boilerplate() {
+        let html = r#"<p>Real content</p>
+            <div class="subscription-widget-wrap"><p>Subscribe to get posts</p></div>
+            <p>Thanks for reading One Useful Thing! Subscribe for free to receive new posts.</p>
+            <p>Share</p>"#;
+        assert_eq!(html_to_text(html), "Real content");
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_scripts() {
+        let html = sanitize_html(r#"<p onclick="x()">Hi<script>alert(1)</script></p>"#);
+        assert_eq!(html, "<p>Hi</p>");
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("héllo world", 5), "héllo...");
+        assert_eq!(truncate_chars("short", 10), "short");
+    }
+}
@@ -0,0 +1,380 @@
+//! Shared HTTP fetching for page-level requests (article pages, discovery, scraping)
+//! Applies a per-host rate limit and honours robots.txt. URLs supplied by users go through
+//! `get_public_text`, which refuses hosts that are (or resolve to) non-public addresses.
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use url::Url;
+
+pub const USER_AGENT: &str = "AI-Dashboard-Ingestor/0.1";
+
+/// Token matched against `User-agent:` lines in robots.txt
+const ROBOTS_AGENT: &str = "ai-dashboard-ingestor";
+
+/// Minimum delay between two requests to the same host
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Redirects followed for user-supplied URLs; each hop is checked again
+const MAX_PUBLIC_REDIRECTS: usize = 10;
+
+pub struct Fetcher {
+    client: reqwest::Client,
+    /// Only connects to public addresses, including after redirects
+    public_client: reqwest::Client,
+    min_interval: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+    robots: Mutex<HashMap<String, RobotsRules>>,
+}
+
+/// Process-wide fetcher so every ingestor shares the same per-host limits
+pub fn shared() -> &'static Fetcher {
+    static FETCHER: OnceLock<Fetcher> = OnceLock::new();
+    FETCHER.get_or_init(|| Fetcher::new(DEFAULT_MIN_INTERVAL))
+}
+
+impl Fetcher {
+    pub fn new(min_interval: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("failed to build HTTP client");
+        let public_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(reqwest::redirect::Policy::custom(|attempt| {
+                if attempt.previous().len() >= MAX_PUBLIC_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if let Err(e) = check_public_url(attempt.url()) {
+                    attempt.error(e)
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .expect("failed to build HTTP client");
+
+        Self {
+            client,
+            public_client,
+            min_interval,
+            next_slot: Mutex::new(HashMap::new()),
+            robots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until the host's rate limit allows another request
+    pub async fn throttle(&self, url: &Url) {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let interval = self.min_interval;
+
+        let wait = {
+            let mut slots = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = slots.get(&host).copied().unwrap_or(now).max(now);
+            slots.insert(host, slot + interval);
+            slot - now
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Check robots.txt for the URL's host (fetched once per host and cached)
+    pub async fn is_allowed(&self, url: &Url) -> bool {
+        self.robots_allow(url, &self.client).await
+    }
+
+    /// `is_allowed`, fetching a missing robots.txt with `client`
+    async fn robots_allow(&self, url: &Url, client: &reqwest::Client) -> bool {
+        let origin = url.origin().ascii_serialization();
+
+        let cached = self.robots.lock().unwrap().get(&origin).cloned();
+        let rules = match cached {
+            Some(rules) => rules,
+            None => {
+                let rules = self.fetch_robots(&origin, client).await;
+                self.robots.lock().unwrap().insert(origin, rules.clone());
+                rules
+            }
+        };
+
+        rules.is_allowed(url.path())
+    }
+
+    async fn fetch_robots(&self, origin: &str, client: &reqwest::Client) -> RobotsRules {
+        let robots_url = format!("{}/robots.txt", origin);
+        if let Ok(parsed) = Url::parse(&robots_url) {
+            self.throttle(&parsed).await;
+        }
+        let response = match client.get(&robots_url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                log::debug!("Could not fetch {}: {}", robots_url, e);
+                return RobotsRules::default();
+            }
+        };
+
+        // Missing robots.txt means everything is allowed
+        if !response.status().is_success() {
+            return RobotsRules::default();
+        }
+
+        match response.text().await {
+            Ok(text) => RobotsRules::parse(&text, ROBOTS_AGENT),
+            Err(_) => RobotsRules::default(),
+        }
+    }
+
+    /// Fetch a page as text, respecting robots.txt and the per-host rate limit
+    pub async fn get_text(&self, url: &str) -> Result<String> {
+        let parsed = Url::parse(url)?;
+        if !self.is_allowed(&parsed).await {
+            return Err(anyhow!("Disallowed by robots.txt: {}", url));
+        }
+
+        self.throttle(&parsed).await;
+        let text = self
+            .client
+            .get(parsed)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(text)
+    }
+
+    /// Like `get_text`, for URLs from outside (submissions): loopback, private, link-local and
+    /// other non-public addresses are refused, whether literal, resolved or redirected to
+    pub async fn get_public_text(&self, url: &str) -> Result<String> {
+        let parsed = Url::parse(url)?;
+        check_public_url(&parsed)?;
+        for addr in tokio::net::lookup_host((parsed.host_str().unwrap_or_default(), 0)).await? {
+            if !is_public_ip(addr.ip()) {
+                return Err(anyhow!("{} resolves to a non-public address", url));
+            }
+        }
+        if !self.robots_allow(&parsed, &self.public_client).await {
+            return Err(anyhow!("Disallowed by robots.txt: {}", url));
+        }
+
+        self.throttle(&parsed).await;
+        let text = self
+            .public_client
+            .get(parsed)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(text)
+    }
+}
+
+/// http(s) URL whose host, if an IP literal, is public. Domains are checked when resolved.
+fn check_public_url(url: &Url) -> Result<()> {
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(anyhow!("Only http(s) URLs can be fetched: {}", url));
+    }
+    let ip = match url.host() {
+        Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(url::Host::Domain(_)) => return Ok(()),
+        None => return Err(anyhow!("URL has no host: {}", url)),
+    };
+    if !is_public_ip(ip) {
+        return Err(anyhow!("{} is not a public address", ip));
+    }
+    Ok(())
+}
+
+/// Globally routable unicast address: not loopback, private, link-local, shared (CGNAT),
+/// unspecified, broadcast, multicast, documentation, benchmarking or reserved space.
+/// IPv6 prefixes that embed an IPv4 address (NAT64 `64:ff9b::/96`, 6to4 `2002::/16`) are refused outright
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && c == 0)
+                || (a == 198 && (b == 18 || b == 19))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || (first == 0x0064 && ip.segments()[1] == 0xff9b && ip.segments()[2..6] == [0; 4])
+                || first == 0x2002
+                || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+/// DNS resolver for `public_client`: a name with any non-public address is refused, so a
+/// redirect (or a changed DNS answer) can't point the connection at an internal host
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if addrs.is_empty() || addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+                return Err(format!("{} does not resolve to public addresses only", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Allow/Disallow rules from the robots.txt group that applies to us
+#[derive(Debug, Clone, Default)]
+pub struct RobotsRules {
+    rules: Vec<(bool, String)>, // (allow, path prefix)
+}
+
+impl RobotsRules {
+    /// Parse robots.txt, keeping the group for `agent` or, failing that, the `*` group
+    pub fn parse(text: &str, agent: &str) -> Self {
+        let agent = agent.to_lowercase();
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+        let mut found_specific = false;
+
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group
+                    if in_rules {
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow allows everything
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (key == "allow", value.to_string());
+                    if group_agents.iter().any(|a| agent.contains(a.as_str()) && a != "*") {
+                        found_specific = true;
+                        specific.push(rule.clone());
+                    }
+                    if group_agents.iter().any(|a| a == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if found_specific { specific } else { wildcard },
+        }
+    }
+
+    /// Longest matching rule wins; Allow wins ties
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, prefix)| path.starts_with(prefix.trim_end_matches('*')))
+            .max_by_key(|(allow, prefix)| (prefix.len(), *allow))
+            .map(|(allow, _)| *allow)
+            .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.215.14", "2606:2800:21f:cb07:6820:80da:af6b:8b2c", "1.1.1.1"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1",
+            "0.0.0.0", "::1", "fe80::1", "fd00::1", "::ffff:127.0.0.1", "::ffff:169.254.169.254",
+            "64:ff9b::7f00:1", "64:ff9b::a9fe:a9fe", "2002:7f00:1::1", "2002:c0a8:101::1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_public_text_refuses_local_hosts() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = axum::Router::new().route("/", axum::routing::get(|| async { "internal" }));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let fetcher = Fetcher::new(Duration::ZERO);
+        assert_eq!(fetcher.get_text(&format!("http://127.0.0.1:{}/", port)).await.unwrap(), "internal");
+        for url in [
+            format!("http://127.0.0.1:{}/", port),
+            format!("http://localhost:{}/", port),
+            format!("http://[::1]:{}/", port),
+            "http://169.254.169.254/latest/meta-data/".to_string(),
+            "file:///etc/passwd".to_string(),
+        ] {
+            assert!(fetcher.get_public_text(&url).await.is_err(), "{}", url);
+        }
+    }
+
+    const ROBOTS: &str = "User-agent: *\nDisallow: /private/\nAllow: /private/public\n\nUser-agent: BadBot\nDisallow: /\n";
+
+    #[test]
+    fn test_robots_wildcard_group() {
+        let rules = RobotsRules::parse(ROBOTS, ROBOTS_AGENT);
+        assert!(rules.is_allowed("/blog/post"));
+        assert!(!rules.is_allowed("/private/notes"));
+        assert!(rules.is_allowed("/private/public/page"));
+    }
+
+    #[test]
+    fn test_robots_specific_group_overrides_wildcard() {
+        let rules = RobotsRules::parse(ROBOTS, "badbot");
+        assert!(!rules.is_allowed("/blog/post"));
+    }
+
+    #[test]
+    fn test_robots_empty_disallow_allows_all() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow:\n", ROBOTS_AGENT);
+        assert!(rules.is_allowed("/anything"));
+    }
+}
@@ -1,10 +1,20 @@
+mod cli;
 mod config;
 mod db;
+mod discover;
+mod fulltext;
+mod html;
+mod http;
 mod models;
+mod pages;
+mod server;
 mod sources;
 mod topics;
+mod urls;
+mod websub;
 
 use anyhow::Result;
+use cli::Command;
 use config::Config;
 use db::create_pool;
 use sources::run_ingestion_cycle;
@@ -19,6 +29,15 @@ async fn main() -> Result<()> {
     // .env (optional locally; on Render you'll use env vars)
     dotenvy::dotenv().ok();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = Command::parse(&args)?;
+
+    if !command.needs_database() {
+        if let Command::DiscoverSource { url, .. } = &command {
+            return cli::discover_source(None, url).await;
+        }
+    }
+
     // Load config (includes DATABASE_URL, ARXIV_API_URL, etc.)
     let config = Config::from_env()?;
 
@@ -33,9 +52,28 @@ async fn main() -> Result<()> {
         .await?;
     log::info!("Database connection successful: {:?}", result);
 
+    match topics::load(&pool).await {
+        Ok(count) => log::info!("Loaded {} topics", count),
+        Err(e) => log::warn!("Failed to load topic taxonomy, using the {} seeded topics: {e}", topics::current_len()),
+    }
+
+    match command {
+        Command::DiscoverSource { url, .. } => {
+            return cli::discover_source(Some(&pool), &url).await;
+        }
+        Command::BackfillSource { source_id, max_pages, since } => {
+            return cli::backfill_source(&pool, source_id, max_pages, since).await;
+        }
+        Command::Submit { url, source_id } => {
+            return cli::submit(&pool, &url, source_id).await;
+        }
+        Command::Daemon => return run_daemon(&config, pool).await,
+        Command::Ingest => {}
+    }
+
     // Single ingestion cycle (no loop)
     log::info!("Starting single ingestion cycle (cron mode)...");
-    match run_ingestion_cycle(&pool).await {
+    match run_ingestion_cycle(&pool, &config).await {
         Ok(count) => {
             log::info!("Ingestion cycle completed: {} items inserted/updated", count);
         }
@@ -48,3 +86,39 @@ async fn main() -> Result<()> {
     log::info!("Ingestion cycle finished; exiting.");
     Ok(())
 }
+
+/// Long-running mode: HTTP endpoints (WebSub callbacks, link submissions) plus an ingestion cycle every interval
+async fn run_daemon(config: &Config, pool: sqlx::PgPool) -> Result<()> {
+    let server_pool = pool.clone();
+    let server_config = config.clone();
+    tokio::spawn(async move {
+        if let Err(e) = server::serve(server_pool, &server_config).await {
+            log::error!("HTTP server stopped: {e}");
+        }
+    });
+
+    let interval = std::time::Duration::from_secs(config.ingestion_interval_secs);
+    loop {
+        // Pick up taxonomy edits without a restart
+        if let Err(e) = topics::load(&pool).await {
+            log::error!("Failed to reload topic taxonomy, keeping the {} topics in use: {e}", topics::current_len());
+        }
+
+        log::info!("Starting ingestion cycle (daemon mode)...");
+        match run_ingestion_cycle(&pool, config).await {
+            Ok(count) => log::info!("Ingestion cycle completed: {} items inserted/updated", count),
+            Err(e) => log::error!("Ingestion cycle failed: {e}"),
+        }
+
+        // Subscribe after polling so newly seen hub links are picked up
+        if let (Some(callback_base_url), Some(secret)) = (&config.websub_callback_base_url, &config.websub_secret) {
+            match websub::renew_subscriptions(&pool, callback_base_url, secret).await {
+                Ok(count) if count > 0 => log::info!("Sent {} WebSub subscription requests", count),
+                Ok(_) => {}
+                Err(e) => log::error!("WebSub renewal failed: {e}"),
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
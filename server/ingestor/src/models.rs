@@ -15,6 +15,32 @@ pub enum SourceType {
     TwitterApi,
     #[serde(rename = "manual")]
     Manual,
+    #[serde(rename = "email")]
+    Email,
+    #[serde(rename = "mastodon")]
+    Mastodon,
+    #[serde(rename = "bluesky")]
+    Bluesky,
+    #[serde(rename = "hackernews")]
+    HackerNews,
+    #[serde(rename = "reddit")]
+    Reddit,
+    #[serde(rename = "github")]
+    GitHub,
+    #[serde(rename = "huggingface")]
+    HuggingFace,
+    #[serde(rename = "youtube")]
+    YouTube,
+    #[serde(rename = "openreview")]
+    OpenReview,
+    #[serde(rename = "biorxiv")]
+    BioRxiv,
+    #[serde(rename = "edgar")]
+    Edgar,
+    #[serde(rename = "scrape")]
+    Scrape,
+    #[serde(rename = "sitemap")]
+    Sitemap,
 }
 
 impl SourceType {
@@ -24,6 +50,19 @@ impl SourceType {
             SourceType::Rss => "rss",
             SourceType::TwitterApi => "twitter_api",
             SourceType::Manual => "manual",
+            SourceType::Email => "email",
+            SourceType::Mastodon => "mastodon",
+            SourceType::Bluesky => "bluesky",
+            SourceType::HackerNews => "hackernews",
+            SourceType::Reddit => "reddit",
+            SourceType::GitHub => "github",
+            SourceType::HuggingFace => "huggingface",
+            SourceType::YouTube => "youtube",
+            SourceType::OpenReview => "openreview",
+            SourceType::BioRxiv => "biorxiv",
+            SourceType::Edgar => "edgar",
+            SourceType::Scrape => "scrape",
+            SourceType::Sitemap => "sitemap",
         }
     }
 }
@@ -40,6 +79,10 @@ pub enum Medium {
     Blog,
     #[serde(rename = "tweet")]
     Tweet,
+    #[serde(rename = "podcast")]
+    Podcast,
+    #[serde(rename = "video")]
+    Video,
 }
 
 impl Medium {
@@ -49,6 +92,8 @@ impl Medium {
             Medium::Newsletter => "newsletter",
             Medium::Blog => "blog",
             Medium::Tweet => "tweet",
+            Medium::Podcast => "podcast",
+            Medium::Video => "video",
         }
     }
 }
@@ -60,7 +105,7 @@ pub struct Source {
     pub name: String,
     #[sqlx(rename = "type")]
     pub source_type: String, // Stored as 'arxiv', 'rss', etc.
-    pub medium: String,      // Stored as 'paper', 'newsletter', etc.
+    pub medium: String,      // Stored as 'paper', 'newsletter', 'podcast', etc.
     pub ingest_url: Option<String>,
     pub active: bool,
     pub frequency: Option<String>,
@@ -69,6 +114,25 @@ pub struct Source {
     pub updated_at: DateTime<Utc>,
 }
 
+#[cfg(test)]
+impl Source {
+    /// Fixture for ingestor tests: source #1, active, without an `ingest_url`
+    pub fn test(source_type: &str, medium: &str, meta: serde_json::Value) -> Self {
+        Self {
+            id: 1,
+            name: format!("Test {}", source_type),
+            source_type: source_type.to_string(),
+            medium: medium.to_string(),
+            ingest_url: None,
+            active: true,
+            frequency: None,
+            meta,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
 impl Source {
     pub fn source_type(&self) -> Option<SourceType> {
         match self.source_type.as_str() {
@@ -76,6 +140,19 @@ impl Source {
             "rss" => Some(SourceType::Rss),
             "twitter_api" => Some(SourceType::TwitterApi),
             "manual" => Some(SourceType::Manual),
+            "email" => Some(SourceType::Email),
+            "mastodon" => Some(SourceType::Mastodon),
+            "bluesky" => Some(SourceType::Bluesky),
+            "hackernews" => Some(SourceType::HackerNews),
+            "reddit" => Some(SourceType::Reddit),
+            "github" => Some(SourceType::GitHub),
+            "huggingface" => Some(SourceType::HuggingFace),
+            "youtube" => Some(SourceType::YouTube),
+            "openreview" => Some(SourceType::OpenReview),
+            "biorxiv" => Some(SourceType::BioRxiv),
+            "edgar" => Some(SourceType::Edgar),
+            "scrape" => Some(SourceType::Scrape),
+            "sitemap" => Some(SourceType::Sitemap),
             _ => None,
         }
     }
@@ -86,6 +163,8 @@ impl Source {
             "newsletter" => Some(Medium::Newsletter),
             "blog" => Some(Medium::Blog),
             "tweet" => Some(Medium::Tweet),
+            "podcast" => Some(Medium::Podcast),
+            "video" => Some(Medium::Video),
             _ => None,
         }
     }
@@ -97,6 +176,7 @@ pub struct Item {
     pub id: Uuid,
     pub source_id: i32,
     pub source_type: String, // Mirrored from sources.medium for quick filtering
+    pub external_id: Option<String>, // Stable id from the source (arXiv id, feed guid, tweet id)
     pub title: String,
     pub url: String,
     pub summary: Option<String>,
@@ -114,11 +194,28 @@ impl Item {
             "newsletter" => Some(Medium::Newsletter),
             "blog" => Some(Medium::Blog),
             "tweet" => Some(Medium::Tweet),
+            "podcast" => Some(Medium::Podcast),
+            "video" => Some(Medium::Video),
             _ => None,
         }
     }
 }
 
+/// A topic of the taxonomy, matched against item text by its keywords and aliases
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Topic {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub color: String,
+    pub aliases: Vec<String>,
+    pub keywords: Vec<String>,
+    pub position: i32,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Represents a topic tag for an item
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct ItemTopic {
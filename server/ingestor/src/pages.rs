@@ -0,0 +1,242 @@
+//! Metadata of a single web page: title, description, author, publish date and OpenGraph fields
+//! Used by sources that only know a URL (sitemaps, manual submissions); `parse_date` is shared
+//! with listing scrapes
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use scraper::{Html, Selector};
+use url::Url;
+
+/// Formats `parse_date` tries when no explicit format is given (after RFC 3339 / RFC 2822)
+const COMMON_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%B %d, %Y",
+    "%b %d, %Y",
+    "%d %B %Y",
+    "%d %b %Y",
+    "%m/%d/%Y",
+    "%Y/%m/%d",
+];
+
+/// What a page says about itself, most specific source first (OpenGraph, meta tags, JSON-LD, markup)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+    pub og_type: Option<String>,
+    pub canonical_url: Option<String>,
+}
+
+impl PageMetadata {
+    /// The OpenGraph / page fields for `raw_metadata`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "author": self.author,
+            "image_url": self.image_url,
+            "site_name": self.site_name,
+            "og_type": self.og_type,
+            "canonical_url": self.canonical_url,
+        })
+    }
+}
+
+pub fn extract_page_metadata(html: &str, base: &Url) -> PageMetadata {
+    let document = Html::parse_document(html);
+
+    let meta = |keys: &[&str]| -> Option<String> {
+        keys.iter().find_map(|key| {
+            let selector = Selector::parse(&format!(r#"meta[property="{0}"], meta[name="{0}"]"#, key)).ok()?;
+            document
+                .select(&selector)
+                .filter_map(|el| el.value().attr("content"))
+                .map(clean_text)
+                .find(|content| !content.is_empty())
+        })
+    };
+    let first_text = |css: &str| -> Option<String> {
+        let selector = Selector::parse(css).ok()?;
+        document
+            .select(&selector)
+            .map(|el| clean_text(&el.text().collect::<String>()))
+            .find(|text| !text.is_empty())
+    };
+    let json_ld = json_ld_article(&document);
+    let json_ld_text = |key: &str| {
+        json_ld
+            .as_ref()
+            .and_then(|ld| ld.get(key))
+            .and_then(json_ld_string)
+            .map(|s| clean_text(&s))
+            .filter(|s| !s.is_empty())
+    };
+
+    let title = meta(&["og:title", "twitter:title"])
+        .or_else(|| json_ld_text("headline"))
+        .or_else(|| first_text("title"))
+        .or_else(|| first_text("h1"));
+    let description = meta(&["og:description", "description", "twitter:description"])
+        .or_else(|| json_ld_text("description"));
+    let author = meta(&["author", "article:author", "parsely-author", "dc.creator"])
+        .or_else(|| json_ld_text("author"))
+        .filter(|author| !author.starts_with("http"));
+
+    let published_at = meta(&["article:published_time", "og:published_time", "date", "pubdate", "publish-date", "dc.date"])
+        .or_else(|| json_ld_text("datePublished"))
+        .or_else(|| {
+            let selector = Selector::parse("time[datetime]").ok()?;
+            document
+                .select(&selector)
+                .find_map(|el| el.value().attr("datetime").map(str::to_string))
+        })
+        .and_then(|text| parse_date(&text, None));
+
+    let resolve = |href: String| base.join(href.trim()).ok().map(|url| url.to_string());
+    let image_url = meta(&["og:image", "og:image:url", "twitter:image"]).and_then(resolve);
+    let canonical_url = crate::urls::find_canonical_link(html, base)
+        .or_else(|| meta(&["og:url"]).and_then(resolve));
+
+    PageMetadata {
+        title,
+        description,
+        author,
+        published_at,
+        image_url,
+        site_name: meta(&["og:site_name"]),
+        og_type: meta(&["og:type"]),
+        canonical_url,
+    }
+}
+
+fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The first JSON-LD object describing an article-like thing (also inside `@graph`)
+fn json_ld_article(document: &Html) -> Option<serde_json::Value> {
+    let selector = Selector::parse(r#"script[type="application/ld+json"]"#).ok()?;
+    let is_article = |value: &serde_json::Value| {
+        let types: Vec<&str> = match value.get("@type") {
+            Some(serde_json::Value::String(t)) => vec![t.as_str()],
+            Some(serde_json::Value::Array(ts)) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        types.iter().any(|t| t.ends_with("Article") || *t == "BlogPosting" || *t == "WebPage")
+    };
+
+    document
+        .select(&selector)
+        .filter_map(|script| serde_json::from_str::<serde_json::Value>(&script.text().collect::<String>()).ok())
+        .flat_map(|value| match value {
+            serde_json::Value::Array(values) => values,
+            serde_json::Value::Object(ref obj) if obj.contains_key("@graph") => {
+                obj["@graph"].as_array().cloned().unwrap_or_default()
+            }
+            other => vec![other],
+        })
+        .find(is_article)
+}
+
+/// JSON-LD values are strings, `{"name": ...}` objects, or lists of either
+fn json_ld_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Object(obj) => obj.get("name").and_then(|n| n.as_str()).map(str::to_string),
+        serde_json::Value::Array(values) => {
+            let names: Vec<String> = values.iter().filter_map(json_ld_string).collect();
+            (!names.is_empty()).then(|| names.join(", "))
+        }
+        _ => None,
+    }
+}
+
+/// Parse a date with an explicit chrono format, or RFC 3339 / RFC 2822 / common formats
+pub fn parse_date(text: &str, format: Option<&str>) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let naive_date = |format: &str| {
+        NaiveDateTime::parse_from_str(text, format)
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(text, format).ok().and_then(|d| d.and_hms_opt(0, 0, 0)))
+            .map(|dt| Utc.from_utc_datetime(&dt))
+    };
+
+    if let Some(format) = format {
+        return DateTime::parse_from_str(text, format)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|| naive_date(format));
+    }
+
+    DateTime::parse_from_rfc3339(text)
+        .or_else(|_| DateTime::parse_from_rfc2822(text))
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| COMMON_DATE_FORMATS.iter().find_map(|format| naive_date(format)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_extract_opengraph_metadata() {
+        let html = r#"<html><head>
+            <title>Fallback title | Example</title>
+            <meta property="og:title" content="Scaling  laws for agents">
+            <meta name="description" content="How agents scale.">
+            <meta property="og:image" content="/images/cover.png">
+            <meta property="og:site_name" content="Example Research">
+            <meta property="article:published_time" content="2026-01-05T09:00:00Z">
+            <meta name="author" content="Ada Lovelace">
+            <link rel="canonical" href="https://example.com/research/scaling">
+        </head><body><h1>Ignored</h1></body></html>"#;
+        let base = Url::parse("https://example.com/research/scaling?ref=home").unwrap();
+
+        let page = extract_page_metadata(html, &base);
+        assert_eq!(page.title.as_deref(), Some("Scaling laws for agents"));
+        assert_eq!(page.description.as_deref(), Some("How agents scale."));
+        assert_eq!(page.author.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(page.published_at, Some(Utc.with_ymd_and_hms(2026, 1, 5, 9, 0, 0).unwrap()));
+        assert_eq!(page.image_url.as_deref(), Some("https://example.com/images/cover.png"));
+        assert_eq!(page.canonical_url.as_deref(), Some("https://example.com/research/scaling"));
+    }
+
+    #[test]
+    fn test_extract_json_ld_metadata() {
+        let html = r#"<html><head><title>Post</title>
+            <script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+                {"@type": "Organization", "name": "Example"},
+                {"@type": "BlogPosting", "headline": "From JSON-LD", "datePublished": "2026-01-02",
+                 "author": [{"@type": "Person", "name": "Ada Lovelace"}, {"@type": "Person", "name": "Alan Turing"}]}
+            ]}</script>
+        </head><body><p>Hello</p></body></html>"#;
+        let base = Url::parse("https://example.com/post").unwrap();
+
+        let page = extract_page_metadata(html, &base);
+        assert_eq!(page.title.as_deref(), Some("From JSON-LD"));
+        assert_eq!(page.author.as_deref(), Some("Ada Lovelace, Alan Turing"));
+        assert_eq!(page.published_at, Some(Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap()));
+        assert_eq!(page.description, None);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("05.01.2026", Some("%d.%m.%Y")),
+            Some(Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_date("Mon, 05 Jan 2026 09:00:00 +0100", None),
+            Some(Utc.with_ymd_and_hms(2026, 1, 5, 8, 0, 0).unwrap())
+        );
+        assert_eq!(parse_date("Jan 5, 2026", None), Some(Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap()));
+        assert_eq!(parse_date("soon", None), None);
+    }
+}
@@ -0,0 +1,144 @@
+//! HTTP endpoints served in daemon mode (WebSub callbacks, link submissions, health check)
+
+use anyhow::Result;
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use sqlx::PgPool;
+use subtle::ConstantTimeEq;
+
+use crate::config::Config;
+use crate::websub::VerificationRequest;
+
+#[derive(Clone)]
+struct AppState {
+    pool: PgPool,
+    websub_secret: Option<String>,
+    submit_token: Option<String>,
+}
+
+pub fn router(pool: PgPool, config: &Config) -> Router {
+    Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .route("/websub/{source_id}", get(websub_verify).post(websub_push))
+        .route("/submit", post(submit))
+        .with_state(AppState {
+            pool,
+            websub_secret: config.websub_secret.clone(),
+            submit_token: config.submit_token.clone(),
+        })
+}
+
+pub async fn serve(pool: PgPool, config: &Config) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(&config.http_bind_addr).await?;
+    log::info!("HTTP server listening on {}", config.http_bind_addr);
+    axum::serve(listener, router(pool, config)).await?;
+    Ok(())
+}
+
+/// Hub intent verification: echo `hub.challenge` to confirm, 404 to refuse
+async fn websub_verify(
+    State(state): State<AppState>,
+    Path(source_id): Path<i32>,
+    Query(request): Query<VerificationRequest>,
+) -> (StatusCode, String) {
+    match crate::websub::verify_intent(&state.pool, source_id, &request).await {
+        Ok(Some(challenge)) => (StatusCode::OK, challenge),
+        Ok(None) => (StatusCode::NOT_FOUND, String::new()),
+        Err(e) => {
+            log::error!("WebSub verification for source {} failed: {}", source_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+/// Content distribution. Always acknowledged so hubs don't retry rejected payloads.
+async fn websub_push(
+    State(state): State<AppState>,
+    Path(source_id): Path<i32>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let signature = headers
+        .get("X-Hub-Signature")
+        .and_then(|v| v.to_str().ok());
+
+    let Some(secret) = &state.websub_secret else {
+        log::warn!("Ignoring WebSub push for source {}: WEBSUB_SECRET is not set", source_id);
+        return StatusCode::ACCEPTED;
+    };
+
+    match crate::websub::receive_push(&state.pool, secret, source_id, signature, &body).await {
+        Ok(count) => log::info!("WebSub push for source {}: {} items", source_id, count),
+        Err(e) => log::warn!("Ignoring WebSub push for source {}: {}", source_id, e),
+    }
+
+    StatusCode::ACCEPTED
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitRequest {
+    url: String,
+    source_id: i32,
+}
+
+/// Store a link under a manual source. Requires `Authorization: Bearer <SUBMIT_TOKEN>`;
+/// 404 when no token is configured so the endpoint doesn't exist by default.
+async fn submit(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<SubmitRequest>,
+) -> Result<Json<crate::sources::manual::Submission>, (StatusCode, String)> {
+    let Some(token) = &state.submit_token else {
+        return Err((StatusCode::NOT_FOUND, String::new()));
+    };
+    if !is_authorized(&headers, token) {
+        return Err((StatusCode::UNAUTHORIZED, "Invalid or missing bearer token".to_string()));
+    }
+
+    let source = match crate::db::get_source(&state.pool, request.source_id).await {
+        Ok(Some(source)) => source,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, format!("No source with id {}", request.source_id))),
+        Err(e) => {
+            log::error!("Failed to load source {}: {}", request.source_id, e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, String::new()));
+        }
+    };
+
+    crate::sources::manual::submit_url(&state.pool, &source, &request.url)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            // Details (fetch errors, resolved addresses) stay in the log
+            log::warn!("Submission of {} to source {} failed: {}", request.url, request.source_id, e);
+            (StatusCode::UNPROCESSABLE_ENTITY, "Could not add this URL".to_string())
+        })
+}
+
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|given| bool::from(given.trim().as_bytes().ct_eq(token.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_authorized() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_authorized(&headers, "secret"));
+
+        headers.insert("Authorization", "Bearer wrong".parse().unwrap());
+        assert!(!is_authorized(&headers, "secret"));
+
+        headers.insert("Authorization", "Bearer secret".parse().unwrap());
+        assert!(is_authorized(&headers, "secret"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Archived / paged feed traversal (RFC 5005) for backfilling an RSS source's history
+//! Configured per source with `meta.backfill = { "max_pages": 10, "since": "2024-01-01" }`
+
+use chrono::{DateTime, NaiveDate, Utc};
+use url::Url;
+
+/// Pages fetched beyond the current feed document when the source doesn't set `max_pages`
+pub const DEFAULT_MAX_PAGES: u32 = 10;
+
+/// How far back to follow a feed's archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillOptions {
+    pub max_pages: u32,
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl BackfillOptions {
+    /// Read `meta.backfill`; `max_pages = 0` disables backfilling on first ingestion
+    pub fn from_meta(meta: &serde_json::Value) -> Self {
+        let config = meta.get("backfill");
+        let max_pages = config
+            .and_then(|c| c.get("max_pages"))
+            .and_then(|v| v.as_u64())
+            .map(|pages| pages.min(u32::MAX as u64) as u32)
+            .unwrap_or(DEFAULT_MAX_PAGES);
+        let since = config
+            .and_then(|c| c.get("since"))
+            .and_then(|v| v.as_str())
+            .and_then(parse_since);
+
+        Self { max_pages, since }
+    }
+}
+
+/// Accepts an RFC 3339 timestamp or a plain `YYYY-MM-DD` date
+pub fn parse_since(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
+}
+
+/// The older page of a feed: `prev-archive` for archived feeds, `next` for paged feeds
+pub fn older_page_url(links: &[feed_rs::model::Link], page_url: &str) -> Option<String> {
+    let href = ["prev-archive", "next"].iter().find_map(|rel| {
+        links
+            .iter()
+            .find(|link| link.rel.as_deref() == Some(*rel))
+            .map(|link| link.href.as_str())
+    })?;
+
+    // Links may be relative to the page they appear on
+    let base = Url::parse(page_url).ok()?;
+    base.join(href.trim()).ok().map(|url| url.to_string())
+}
+
+/// WordPress feeds have no paging links but accept `?paged=N`
+pub fn wordpress_page_url(feed_url: &str, page: u32) -> Option<String> {
+    let mut url = Url::parse(feed_url).ok()?;
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "paged")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair("paged", &page.to_string());
+    Some(url.to_string())
+}
+
+pub fn is_wordpress(generator: Option<&str>) -> bool {
+    generator
+        .map(|g| g.to_lowercase().contains("wordpress"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARCHIVED_FEED: &str = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Example</title>
+  <id>urn:example</id>
+  <updated>2026-01-01T00:00:00Z</updated>
+  <link rel="current" href="https://example.com/feed.xml"/>
+  <link rel="prev-archive" href="/archive/2025-12.xml"/>
+  <link rel="next" href="https://example.com/feed.xml?page=2"/>
+</feed>"#;
+
+    #[test]
+    fn test_older_page_url_prefers_prev_archive() {
+        let feed = feed_rs::parser::parse(ARCHIVED_FEED.as_bytes()).unwrap();
+        assert_eq!(
+            older_page_url(&feed.links, "https://example.com/feed.xml").as_deref(),
+            Some("https://example.com/archive/2025-12.xml")
+        );
+
+        let paged: Vec<_> = feed
+            .links
+            .into_iter()
+            .filter(|link| link.rel.as_deref() != Some("prev-archive"))
+            .collect();
+        assert_eq!(
+            older_page_url(&paged, "https://example.com/feed.xml").as_deref(),
+            Some("https://example.com/feed.xml?page=2")
+        );
+    }
+
+    #[test]
+    fn test_wordpress_page_url() {
+        assert_eq!(
+            wordpress_page_url("https://blog.example.com/feed/", 2).as_deref(),
+            Some("https://blog.example.com/feed/?paged=2")
+        );
+        assert_eq!(
+            wordpress_page_url("https://blog.example.com/?feed=rss2&paged=2", 3).as_deref(),
+            Some("https://blog.example.com/?feed=rss2&paged=3")
+        );
+    }
+
+    #[test]
+    fn test_backfill_options_from_meta() {
+        let options = BackfillOptions::from_meta(&serde_json::json!({
+            "backfill": { "max_pages": 3, "since": "2025-06-01" }
+        }));
+        assert_eq!(options.max_pages, 3);
+        assert_eq!(options.since.unwrap().to_rfc3339(), "2025-06-01T00:00:00+00:00");
+
+        let defaults = BackfillOptions::from_meta(&serde_json::json!({}));
+        assert_eq!(defaults, BackfillOptions { max_pages: DEFAULT_MAX_PAGES, since: None });
+    }
+}
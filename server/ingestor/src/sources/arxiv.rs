@@ -1,4 +1,3 @@
-use crate::db::insert_or_update_item;
 use crate::models::Item;
 
 // Legacy Paper struct - kept for backwards compatibility during migration
@@ -95,25 +94,7 @@ pub async fn run_arxiv_ingestion(pool: &PgPool, source: &crate::models::Source)
     log::info!("Fetched {} items from ArXiv", items.len());
 
     // Insert or update each item in the database
-    let mut inserted = 0;
-    for item in items {
-        match insert_or_update_item(pool, &item).await {
-            Err(e) => {
-                log::warn!("Failed to insert item {}: {}", item.url, e);
-            }
-            Ok(item_id) => {
-                inserted += 1;
-
-                // Extract and add topics in batch
-                let topics = crate::topics::extract_topics(&item.title, item.summary.as_deref());
-                if !topics.is_empty() {
-                    if let Err(e) = crate::db::add_item_topics_batch(pool, item_id, &topics).await {
-                        log::warn!("Failed to add topics for item {}: {}", item.url, e);
-                    }
-                }
-            }
-        }
-    }
+    let inserted = super::store_items(pool, source, items).await;
 
     log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
 
@@ -265,8 +246,8 @@ fn entry_to_paper(entry: Entry, xml_text: &str) -> Option<Paper> {
 }
 
 fn entry_to_item(entry: Entry, xml_text: &str, source: &crate::models::Source) -> Option<Item> {
-    // Extract arXiv ID from the entry ID (format: http://arxiv.org/abs/XXXX.XXXXX)
-    let external_id = entry.id.split('/').last()?.to_string();
+    // Extract arXiv ID from the entry ID (format: http://arxiv.org/abs/XXXX.XXXXXvN)
+    let (arxiv_id, version) = parse_arxiv_id(&entry.id)?;
 
     // Extract categories
     let mut categories = vec![];
@@ -323,7 +304,8 @@ fn entry_to_item(entry: Entry, xml_text: &str, source: &crate::models::Source) -
 
     // Build raw_metadata with arXiv-specific fields
     let raw_metadata = serde_json::json!({
-        "arxiv_id": external_id,
+        "arxiv_id": arxiv_id,
+        "arxiv_version": version,
         "categories": categories,
         "authors": authors,
         "pdf_url": pdf_url,
@@ -333,8 +315,9 @@ fn entry_to_item(entry: Entry, xml_text: &str, source: &crate::models::Source) -
         id: Uuid::new_v4(),
         source_id: source.id,
         source_type: "paper".to_string(),
+        external_id: Some(arxiv_id),
         title: entry.title.trim().to_string(),
-        url: url?,
+        url: crate::urls::canonicalize_url(&url?),
         summary: entry.summary.map(|s| s.trim().to_string()),
         body: None,
         published_at,
@@ -345,3 +328,55 @@ fn entry_to_item(entry: Entry, xml_text: &str, source: &crate::models::Source) -
 
     Some(item)
 }
+
+/// Split an arXiv entry id into its version-less base id and version number.
+/// Handles both new-style (`2401.12345v2`) and old-style (`hep-th/9901001v1`) ids.
+fn parse_arxiv_id(entry_id: &str) -> Option<(String, Option<u32>)> {
+    let id = match entry_id.split_once("/abs/") {
+        Some((_, id)) => id,
+        None => entry_id.rsplit('/').next()?,
+    };
+    let id = id.trim();
+    if id.is_empty() {
+        return None;
+    }
+
+    if let Some(pos) = id.rfind('v') {
+        if let Ok(version) = id[pos + 1..].parse::<u32>() {
+            if pos > 0 {
+                return Some((id[..pos].to_string(), Some(version)));
+            }
+        }
+    }
+
+    Some((id.to_string(), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_arxiv_id_strips_version() {
+        assert_eq!(
+            parse_arxiv_id("http://arxiv.org/abs/2401.12345v2"),
+            Some(("2401.12345".to_string(), Some(2)))
+        );
+    }
+
+    #[test]
+    fn test_parse_arxiv_id_old_style() {
+        assert_eq!(
+            parse_arxiv_id("http://arxiv.org/abs/hep-th/9901001v1"),
+            Some(("hep-th/9901001".to_string(), Some(1)))
+        );
+    }
+
+    #[test]
+    fn test_parse_arxiv_id_without_version() {
+        assert_eq!(
+            parse_arxiv_id("http://arxiv.org/abs/2401.12345"),
+            Some(("2401.12345".to_string(), None))
+        );
+    }
+}
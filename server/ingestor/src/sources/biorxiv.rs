@@ -0,0 +1,342 @@
+//! bioRxiv / medRxiv ingestion for `biorxiv` sources through the `details` API
+//!
+//! Options in `meta`:
+//!   `server`: `biorxiv` (default) or `medrxiv`, `category` (e.g. `bioinformatics`,
+//!   `health informatics`), and either `days` (default 7) or an explicit
+//!   `interval` of `YYYY-MM-DD/YYYY-MM-DD`; `max_pages` caps cursor paging (default 20)
+//! Like arXiv, the DOI without its version is the external id, so a revised preprint
+//! updates the existing item and `raw_metadata.version` records the latest version.
+//! `ingest_url` overrides the API root (default `https://api.biorxiv.org`).
+
+use crate::html::{html_to_text, truncate_chars};
+use crate::models::{Item, Medium};
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_API_URL: &str = "https://api.biorxiv.org";
+const DEFAULT_DAYS: u32 = 7;
+const DEFAULT_MAX_PAGES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreprintQuery {
+    pub server: String,
+    pub category: Option<String>,
+    /// `YYYY-MM-DD/YYYY-MM-DD` or `Nd` (the last N days)
+    pub interval: String,
+    pub max_pages: usize,
+}
+
+impl PreprintQuery {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        let server = text("server").unwrap_or_else(|| "biorxiv".to_string()).to_lowercase();
+        if server != "biorxiv" && server != "medrxiv" {
+            return Err(anyhow!("Unknown server: {}", server));
+        }
+
+        let interval = match text("interval") {
+            Some(interval) => {
+                let valid = interval
+                    .split_once('/')
+                    .map(|(from, to)| {
+                        NaiveDate::parse_from_str(from, "%Y-%m-%d").is_ok()
+                            && NaiveDate::parse_from_str(to, "%Y-%m-%d").is_ok()
+                    })
+                    .unwrap_or(false);
+                if !valid {
+                    return Err(anyhow!("meta.interval must be YYYY-MM-DD/YYYY-MM-DD"));
+                }
+                interval
+            }
+            None => {
+                let days = meta.get("days").and_then(|v| v.as_u64()).map(|d| d as u32).unwrap_or(DEFAULT_DAYS);
+                format!("{}d", days.max(1))
+            }
+        };
+
+        Ok(Self {
+            server,
+            category: text("category"),
+            interval,
+            max_pages: meta
+                .get("max_pages")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize)
+                .unwrap_or(DEFAULT_MAX_PAGES),
+        })
+    }
+
+    /// The API takes categories lowercased with underscores (`health_informatics`)
+    fn category_param(&self) -> Option<String> {
+        self.category.as_ref().map(|c| c.to_lowercase().replace(' ', "_"))
+    }
+
+    fn matches_category(&self, preprint: &Preprint) -> bool {
+        match self.category_param() {
+            Some(category) => preprint.category.to_lowercase().replace(' ', "_") == category,
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DetailsResponse {
+    #[serde(default)]
+    messages: Vec<DetailsMessage>,
+    #[serde(default)]
+    collection: Vec<Preprint>,
+}
+
+/// Paging info; the API sends numbers as strings or integers depending on the endpoint
+#[derive(Debug, Deserialize)]
+struct DetailsMessage {
+    total: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Preprint {
+    pub doi: String,
+    pub title: String,
+    pub authors: String,
+    pub author_corresponding: Option<String>,
+    pub author_corresponding_institution: Option<String>,
+    pub date: String,
+    pub version: String,
+    #[serde(rename = "type")]
+    pub preprint_type: Option<String>,
+    pub license: Option<String>,
+    #[serde(default)]
+    pub category: String,
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    /// DOI of the journal version, or "NA"
+    pub published: Option<String>,
+    pub server: Option<String>,
+}
+
+impl Preprint {
+    fn version_number(&self) -> u32 {
+        self.version.trim().parse().unwrap_or(1)
+    }
+}
+
+pub async fn run_biorxiv_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let query = PreprintQuery::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid biorxiv source {}: {}", source.name, e))?;
+    let api_url = source
+        .ingest_url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or(DEFAULT_API_URL)
+        .trim_end_matches('/')
+        .to_string();
+
+    log::info!(
+        "Starting {} ingestion for source: {} ({} {:?})",
+        query.server,
+        source.name,
+        query.interval,
+        query.category
+    );
+
+    let preprints = fetch_preprints(&api_url, &query).await?;
+    let items: Vec<Item> = latest_versions(preprints)
+        .iter()
+        .filter(|preprint| query.matches_category(preprint))
+        .map(|preprint| preprint_to_item(preprint, &query.server, source))
+        .collect();
+
+    log::info!("Fetched {} preprints for source: {}", items.len(), source.name);
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+/// Follow the cursor (100 records per page) until the interval's total is reached
+async fn fetch_preprints(api_url: &str, query: &PreprintQuery) -> Result<Vec<Preprint>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let mut preprints = Vec::new();
+    for _ in 0..query.max_pages {
+        let mut request = client.get(format!(
+            "{}/details/{}/{}/{}/json",
+            api_url,
+            query.server,
+            query.interval,
+            preprints.len()
+        ));
+        if let Some(category) = query.category_param() {
+            request = request.query(&[("category", category)]);
+        }
+
+        let response: DetailsResponse = request.send().await?.error_for_status()?.json().await?;
+        let total = response
+            .messages
+            .first()
+            .and_then(|m| m.total.as_ref())
+            .and_then(|t| t.as_u64().or_else(|| t.as_str().and_then(|s| s.parse().ok())))
+            .unwrap_or(0) as usize;
+
+        if response.collection.is_empty() {
+            break;
+        }
+        preprints.extend(response.collection);
+        if preprints.len() >= total {
+            break;
+        }
+    }
+
+    Ok(preprints)
+}
+
+/// An interval can list several versions of one preprint; keep the newest of each DOI
+fn latest_versions(preprints: Vec<Preprint>) -> Vec<Preprint> {
+    let mut by_doi: HashMap<String, Preprint> = HashMap::new();
+    let mut order = Vec::new();
+    for preprint in preprints {
+        let doi = preprint.doi.to_lowercase();
+        match by_doi.get(&doi) {
+            Some(existing) if existing.version_number() >= preprint.version_number() => {}
+            Some(_) => {
+                by_doi.insert(doi, preprint);
+            }
+            None => {
+                order.push(doi.clone());
+                by_doi.insert(doi, preprint);
+            }
+        }
+    }
+
+    order.into_iter().filter_map(|doi| by_doi.remove(&doi)).collect()
+}
+
+fn preprint_to_item(preprint: &Preprint, server: &str, source: &crate::models::Source) -> Item {
+    let version = preprint.version_number();
+    let server = preprint.server.as_deref().unwrap_or(server).to_lowercase();
+    let authors: Vec<&str> = preprint
+        .authors
+        .split(';')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .collect();
+
+    let published_doi = preprint
+        .published
+        .as_deref()
+        .filter(|p| !p.is_empty() && *p != "NA");
+
+    let metadata = serde_json::json!({
+        "doi": preprint.doi,
+        "version": version,
+        "server": server,
+        "category": preprint.category,
+        "categories": [preprint.category],
+        "authors": authors,
+        "corresponding_author": preprint.author_corresponding,
+        "institution": preprint.author_corresponding_institution,
+        "preprint_type": preprint.preprint_type,
+        "license": preprint.license,
+        "published_doi": published_doi,
+        "pdf_url": format!("https://www.{}.org/content/{}v{}.full.pdf", server, preprint.doi, version),
+    });
+
+    let published_at = NaiveDate::parse_from_str(&preprint.date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| Utc.from_utc_datetime(&dt))
+        .unwrap_or_else(Utc::now);
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: Medium::Paper.as_str().to_string(),
+        external_id: Some(preprint.doi.to_lowercase()),
+        title: preprint.title.split_whitespace().collect::<Vec<_>>().join(" "),
+        url: format!("https://www.{}.org/content/{}v{}", server, preprint.doi, version),
+        summary: preprint
+            .abstract_text
+            .as_deref()
+            .map(html_to_text)
+            .filter(|a| !a.is_empty())
+            .map(|a| truncate_chars(&a, 2000)),
+        body: None,
+        published_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preprint(version: &str) -> Preprint {
+        serde_json::from_value(serde_json::json!({
+            "doi": "10.1101/2026.01.05.123456",
+            "title": "Protein language models\n at scale",
+            "authors": "Lovelace, A.; Turing, A.;",
+            "author_corresponding": "Ada Lovelace",
+            "author_corresponding_institution": "Analytical Engine Lab",
+            "date": "2026-01-06",
+            "version": version,
+            "type": "new results",
+            "license": "cc_by",
+            "category": "bioinformatics",
+            "jatsxml": "https://www.biorxiv.org/content/early/2026/01/06/2026.01.05.123456.source.xml",
+            "abstract": "We train...",
+            "published": "NA",
+            "server": "bioRxiv"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_preprint_to_item() {
+        let source = crate::models::Source::test("biorxiv", "paper", serde_json::json!({ "category": "bioinformatics" }));
+
+        let preprints = latest_versions(vec![preprint("1"), preprint("2")]);
+        assert_eq!(preprints.len(), 1);
+
+        let item = preprint_to_item(&preprints[0], "biorxiv", &source);
+        assert_eq!(item.external_id.as_deref(), Some("10.1101/2026.01.05.123456"));
+        assert_eq!(item.url, "https://www.biorxiv.org/content/10.1101/2026.01.05.123456v2");
+        assert_eq!(item.title, "Protein language models at scale");
+        assert_eq!(item.raw_metadata["version"], 2);
+        assert_eq!(item.raw_metadata["authors"], serde_json::json!(["Lovelace, A.", "Turing, A."]));
+        assert!(item.raw_metadata["published_doi"].is_null());
+    }
+
+    #[test]
+    fn test_query_from_meta() {
+        let query = PreprintQuery::from_meta(&serde_json::json!({
+            "server": "medRxiv", "category": "Health Informatics", "days": 3
+        }))
+        .unwrap();
+        assert_eq!(query.server, "medrxiv");
+        assert_eq!(query.interval, "3d");
+        assert_eq!(query.category_param().as_deref(), Some("health_informatics"));
+
+        let query = PreprintQuery::from_meta(&serde_json::json!({ "interval": "2026-01-01/2026-01-31" })).unwrap();
+        assert_eq!(query.interval, "2026-01-01/2026-01-31");
+        assert!(PreprintQuery::from_meta(&serde_json::json!({ "interval": "last week" })).is_err());
+        assert!(PreprintQuery::from_meta(&serde_json::json!({ "server": "chemrxiv" })).is_err());
+    }
+}
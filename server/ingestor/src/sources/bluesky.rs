@@ -0,0 +1,399 @@
+//! Bluesky ingestion for `bluesky` sources over AT Protocol XRPC
+//!
+//! `meta.mode` picks the feed:
+//!   `{"mode": "author", "actor": "someone.bsky.social"}` (`app.bsky.feed.getAuthorFeed`, optional `filter`)
+//!   or `{"mode": "feed", "feed": "at://did:plc:.../app.bsky.feed.generator/name"}` (`app.bsky.feed.getFeed`)
+//! Both are public AppView queries; `ingest_url` overrides the service (default `https://public.api.bsky.app`).
+//! Author feeds stop paging at the last post seen; custom feeds are not chronological, so
+//! they always read `max_pages`.
+
+use crate::models::{Item, Medium};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_SERVICE_URL: &str = "https://public.api.bsky.app";
+
+/// Posts per page (the lexicon maximum)
+const PAGE_LIMIT: &str = "100";
+
+/// Pages fetched per cycle unless `meta.max_pages` says otherwise
+const DEFAULT_MAX_PAGES: u32 = 3;
+
+/// The feed a source follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlueskyFeed {
+    Author { actor: String, filter: Option<String> },
+    Generator { feed_uri: String },
+}
+
+impl BlueskyFeed {
+    pub fn from_meta(meta: &Value) -> Result<Self> {
+        let field = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        match meta.get("mode").and_then(|v| v.as_str()) {
+            Some("author") => Ok(BlueskyFeed::Author {
+                actor: field("actor")
+                    .ok_or_else(|| anyhow!("meta.actor is required"))?
+                    .trim_start_matches('@')
+                    .to_string(),
+                filter: field("filter"),
+            }),
+            Some("feed") => Ok(BlueskyFeed::Generator {
+                feed_uri: field("feed").ok_or_else(|| anyhow!("meta.feed is required"))?,
+            }),
+            Some(mode) => Err(anyhow!("Unknown bluesky mode: {}", mode)),
+            None => Err(anyhow!("meta.mode is required")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedResponse {
+    #[serde(default)]
+    feed: Vec<Value>,
+    cursor: Option<String>,
+}
+
+/// Minimal XRPC query client
+pub struct XrpcClient {
+    client: reqwest::Client,
+    service_url: String,
+}
+
+impl XrpcClient {
+    pub fn new(service_url: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(crate::http::USER_AGENT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            service_url: service_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// `GET /xrpc/<nsid>?params`; XRPC errors come back as `{"error": ..., "message": ...}`
+    pub async fn query<T: serde::de::DeserializeOwned>(&self, nsid: &str, params: &[(&str, &str)]) -> Result<T> {
+        let response = self
+            .client
+            .get(format!("{}/xrpc/{}", self.service_url, nsid))
+            .query(params)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body: Value = response.json().await.unwrap_or_default();
+            return Err(anyhow!(
+                "{} returned {}: {} {}",
+                nsid,
+                status,
+                body.get("error").and_then(|e| e.as_str()).unwrap_or_default(),
+                body.get("message").and_then(|m| m.as_str()).unwrap_or_default()
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+}
+
+pub async fn run_bluesky_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let feed = BlueskyFeed::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid bluesky source {}: {}", source.name, e))?;
+    let service_url = source
+        .ingest_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SERVICE_URL.to_string());
+    let max_pages = source
+        .meta
+        .get("max_pages")
+        .and_then(|v| v.as_u64())
+        .map(|pages| pages.clamp(1, 20) as u32)
+        .unwrap_or(DEFAULT_MAX_PAGES);
+
+    // Newest feed position stored by the previous cycle; author feed paging stops once it is reached
+    let chronological = matches!(feed, BlueskyFeed::Author { .. });
+    let last_seen_at = source
+        .meta
+        .get("bluesky_state")
+        .and_then(|state| state.get("last_seen_at"))
+        .and_then(|v| v.as_str())
+        .and_then(parse_datetime);
+
+    log::info!("Starting Bluesky ingestion for source: {} ({:?})", source.name, feed);
+
+    let client = XrpcClient::new(&service_url)?;
+    let (nsid, mut params) = match &feed {
+        BlueskyFeed::Author { actor, filter } => {
+            let mut params = vec![("actor", actor.as_str())];
+            if let Some(filter) = filter {
+                params.push(("filter", filter.as_str()));
+            }
+            ("app.bsky.feed.getAuthorFeed", params)
+        }
+        BlueskyFeed::Generator { feed_uri } => ("app.bsky.feed.getFeed", vec![("feed", feed_uri.as_str())]),
+    };
+    params.push(("limit", PAGE_LIMIT));
+
+    let mut items = Vec::new();
+    let mut newest_seen: Option<DateTime<Utc>> = None;
+    let mut cursor: Option<String> = None;
+    for _ in 0..max_pages {
+        let mut page_params = params.clone();
+        if let Some(cursor) = cursor.as_deref() {
+            page_params.push(("cursor", cursor));
+        }
+        let page: FeedResponse = client.query(nsid, &page_params).await?;
+
+        let mut reached_seen = false;
+        for entry in &page.feed {
+            if let Some(item) = feed_entry_to_item(entry, source) {
+                let position = feed_position(entry, &item);
+                reached_seen |= last_seen_at.is_some_and(|seen| position <= seen);
+                newest_seen = newest_seen.max(Some(position));
+                items.push(item);
+            }
+        }
+
+        cursor = page.cursor;
+        if cursor.is_none() || (chronological && reached_seen) {
+            break;
+        }
+    }
+
+    log::info!("Fetched {} posts for source: {}", items.len(), source.name);
+
+    if let (true, Some(newest)) = (chronological, newest_seen) {
+        let state = serde_json::json!({ "last_seen_at": last_seen_at.map_or(newest, |seen| seen.max(newest)) });
+        if let Err(e) = crate::db::set_source_meta_key(pool, source.id, "bluesky_state", &state).await {
+            log::warn!("Failed to store Bluesky sync state for source {}: {}", source.name, e);
+        }
+    }
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|d| d.with_timezone(&Utc))
+}
+
+fn str_at<'a>(value: &'a Value, pointer: &str) -> Option<&'a str> {
+    value.pointer(pointer).and_then(|v| v.as_str())
+}
+
+/// Where an entry sits in an author feed: reposts are ordered by when they were reposted
+/// (`reason.indexedAt`), not by the original post's `createdAt`
+fn feed_position(entry: &Value, item: &Item) -> DateTime<Utc> {
+    (str_at(entry, "/reason/$type") == Some("app.bsky.feed.defs#reasonRepost"))
+        .then(|| str_at(entry, "/reason/indexedAt"))
+        .flatten()
+        .and_then(parse_datetime)
+        .unwrap_or(item.published_at)
+}
+
+/// `at://did/app.bsky.feed.post/rkey` -> `https://bsky.app/profile/<handle>/post/<rkey>`
+fn post_web_url(at_uri: &str, handle: &str) -> Option<String> {
+    let rkey = at_uri.strip_prefix("at://")?.rsplit('/').next()?;
+    Some(format!("https://bsky.app/profile/{}/post/{}", handle, rkey))
+}
+
+/// Quoted post from a `record#view` or `recordWithMedia#view` embed
+fn quoted_post(embed: &Value) -> Option<Value> {
+    let record = match str_at(embed, "/$type")? {
+        "app.bsky.embed.record#view" => embed.get("record")?,
+        "app.bsky.embed.recordWithMedia#view" => embed.pointer("/record/record")?,
+        _ => return None,
+    };
+    Some(serde_json::json!({
+        "uri": str_at(record, "/uri")?,
+        "author_handle": str_at(record, "/author/handle"),
+        "text": str_at(record, "/value/text"),
+    }))
+}
+
+/// Link card from an `external#view` embed (alone or alongside a quote)
+fn link_card(embed: &Value) -> Option<Value> {
+    let external = match str_at(embed, "/$type")? {
+        "app.bsky.embed.external#view" => embed.get("external")?,
+        "app.bsky.embed.recordWithMedia#view" => embed.pointer("/media/external")?,
+        _ => return None,
+    };
+    Some(serde_json::json!({
+        "uri": str_at(external, "/uri")?,
+        "title": str_at(external, "/title"),
+        "description": str_at(external, "/description"),
+        "thumb": str_at(external, "/thumb"),
+    }))
+}
+
+/// Map a `FeedViewPost` (post plus reply / repost context) to an item keyed by the post's AT URI
+fn feed_entry_to_item(entry: &Value, source: &crate::models::Source) -> Option<Item> {
+    let post = entry.get("post")?;
+    let uri = str_at(post, "/uri")?.to_string();
+    let handle = str_at(post, "/author/handle")?;
+    let text = str_at(post, "/record/text").unwrap_or_default().trim().to_string();
+
+    let first_line = text.lines().next().unwrap_or_default();
+    let title = format!("@{}: {}", handle, crate::html::truncate_chars(first_line, 120));
+
+    let published_at = str_at(post, "/record/createdAt")
+        .or_else(|| str_at(post, "/indexedAt"))
+        .and_then(parse_datetime)
+        .unwrap_or_else(Utc::now);
+
+    let reply = entry
+        .get("reply")
+        .map(|reply| {
+            serde_json::json!({
+                "parent_uri": str_at(reply, "/parent/uri"),
+                "parent_author_handle": str_at(reply, "/parent/author/handle"),
+                "root_uri": str_at(reply, "/root/uri"),
+            })
+        })
+        .or_else(|| {
+            // Replies whose parent wasn't hydrated still reference it in the record
+            post.pointer("/record/reply").map(|reply| {
+                serde_json::json!({
+                    "parent_uri": str_at(reply, "/parent/uri"),
+                    "root_uri": str_at(reply, "/root/uri"),
+                })
+            })
+        });
+    let embed = post.get("embed");
+    let reposted_by = (str_at(entry, "/reason/$type") == Some("app.bsky.feed.defs#reasonRepost"))
+        .then(|| str_at(entry, "/reason/by/handle"))
+        .flatten();
+
+    let metadata = serde_json::json!({
+        "uri": uri,
+        "cid": str_at(post, "/cid"),
+        "author_did": str_at(post, "/author/did"),
+        "author_handle": handle,
+        "author_name": str_at(post, "/author/displayName"),
+        "likes": post.get("likeCount"),
+        "reposts": post.get("repostCount"),
+        "replies": post.get("replyCount"),
+        "quotes": post.get("quoteCount"),
+        "langs": post.pointer("/record/langs"),
+        "reply": reply,
+        "quote": embed.and_then(quoted_post),
+        "link_card": embed.and_then(link_card),
+        "reposted_by": reposted_by,
+    });
+
+    Some(Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: Medium::Tweet.as_str().to_string(),
+        external_id: Some(uri.clone()),
+        title,
+        url: post_web_url(&uri, handle).unwrap_or(uri),
+        summary: Some(text).filter(|t| !t.is_empty()),
+        body: None,
+        published_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> crate::models::Source {
+        crate::models::Source::test("bluesky", "tweet", serde_json::json!({ "mode": "author", "actor": "alice.example.com" }))
+    }
+
+    #[test]
+    fn test_feed_entry_to_item() {
+        let entry = serde_json::json!({
+            "post": {
+                "uri": "at://did:plc:alice/app.bsky.feed.post/3kabc",
+                "cid": "bafyreia",
+                "author": { "did": "did:plc:alice", "handle": "alice.example.com", "displayName": "Alice" },
+                "record": {
+                    "$type": "app.bsky.feed.post",
+                    "text": "Agreed, and the write-up is good\nmore below",
+                    "createdAt": "2026-01-05T09:00:00.000Z"
+                },
+                "embed": {
+                    "$type": "app.bsky.embed.recordWithMedia#view",
+                    "record": { "record": {
+                        "uri": "at://did:plc:bob/app.bsky.feed.post/3kxyz",
+                        "author": { "handle": "bob.example.com" },
+                        "value": { "text": "New benchmark results" }
+                    } },
+                    "media": {
+                        "$type": "app.bsky.embed.external#view",
+                        "external": { "uri": "https://example.com/post", "title": "Results", "description": "" }
+                    }
+                },
+                "likeCount": 7,
+                "repostCount": 2,
+                "replyCount": 1,
+                "indexedAt": "2026-01-05T09:00:01.000Z"
+            },
+            "reply": {
+                "root": { "uri": "at://did:plc:carol/app.bsky.feed.post/3kroot" },
+                "parent": { "uri": "at://did:plc:carol/app.bsky.feed.post/3kroot", "author": { "handle": "carol.example.com" } }
+            }
+        });
+
+        let item = feed_entry_to_item(&entry, &source()).unwrap();
+        assert_eq!(item.title, "@alice.example.com: Agreed, and the write-up is good");
+        assert_eq!(item.external_id.as_deref(), Some("at://did:plc:alice/app.bsky.feed.post/3kabc"));
+        assert_eq!(item.url, "https://bsky.app/profile/alice.example.com/post/3kabc");
+        assert_eq!(item.raw_metadata["likes"], 7);
+        assert_eq!(item.raw_metadata["reply"]["parent_author_handle"], "carol.example.com");
+        assert_eq!(item.raw_metadata["quote"]["author_handle"], "bob.example.com");
+        assert_eq!(item.raw_metadata["link_card"]["uri"], "https://example.com/post");
+    }
+
+    #[test]
+    fn test_repost_position() {
+        let mut entry = serde_json::json!({
+            "post": {
+                "uri": "at://did:plc:bob/app.bsky.feed.post/3kold",
+                "author": { "did": "did:plc:bob", "handle": "bob.example.com" },
+                "record": { "text": "An older post", "createdAt": "2025-06-01T12:00:00.000Z" }
+            },
+            "reason": {
+                "$type": "app.bsky.feed.defs#reasonRepost",
+                "by": { "handle": "alice.example.com" },
+                "indexedAt": "2026-01-05T09:00:00.000Z"
+            }
+        });
+        let item = feed_entry_to_item(&entry, &source()).unwrap();
+        assert_eq!(item.published_at, parse_datetime("2025-06-01T12:00:00Z").unwrap());
+        assert_eq!(feed_position(&entry, &item), parse_datetime("2026-01-05T09:00:00Z").unwrap());
+
+        entry.as_object_mut().unwrap().remove("reason");
+        assert_eq!(feed_position(&entry, &item), item.published_at);
+    }
+
+    #[test]
+    fn test_feed_from_meta() {
+        assert_eq!(
+            BlueskyFeed::from_meta(&serde_json::json!({ "mode": "author", "actor": "@alice.example.com" })).unwrap(),
+            BlueskyFeed::Author { actor: "alice.example.com".to_string(), filter: None }
+        );
+        assert!(BlueskyFeed::from_meta(&serde_json::json!({ "mode": "feed" })).is_err());
+    }
+}
@@ -0,0 +1,478 @@
+//! SEC EDGAR ingestion for `edgar` sources: company filings or full-text search hits
+//!
+//! `meta` either follows companies or searches filing text:
+//!   `{"ciks": ["320193", "1045810"], "forms": ["10-K", "10-Q", "8-K"]}`
+//!   `{"query": "\"artificial intelligence\" \"risk factors\"", "forms": ["10-K"], "days": 30}`
+//! `forms` is optional (every form type); searches look back `days` (default 30) and read up
+//! to `MAX_SEARCH_PAGES` pages of hits.
+//! The SEC rejects requests without a contact User-Agent, so `SEC_USER_AGENT` is required
+//! (e.g. `Example Corp admin@example.com`). Requests are spaced to stay under 10 per second.
+
+use crate::config::Config;
+use crate::models::Item;
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const SUBMISSIONS_URL: &str = "https://data.sec.gov/submissions";
+const SEARCH_URL: &str = "https://efts.sec.gov/LATEST/search-index";
+const ARCHIVES_URL: &str = "https://www.sec.gov/Archives/edgar/data";
+
+/// SEC fair-access policy allows 10 requests per second
+const REQUEST_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Filings kept per company from its recent-filings list
+const FILINGS_PER_COMPANY: usize = 40;
+const DEFAULT_SEARCH_DAYS: i64 = 30;
+
+/// Full-text search returns 100 hits per page; `from` is the offset of the first
+const SEARCH_PAGE_SIZE: usize = 100;
+const MAX_SEARCH_PAGES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgarQuery {
+    Companies { ciks: Vec<String>, forms: Vec<String> },
+    Search { query: String, forms: Vec<String>, days: i64 },
+}
+
+impl EdgarQuery {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let forms: Vec<String> = meta
+            .get("forms")
+            .and_then(|v| v.as_array())
+            .map(|forms| forms.iter().filter_map(|f| f.as_str()).map(|f| f.trim().to_uppercase()).collect())
+            .unwrap_or_default();
+
+        if let Some(ciks) = meta.get("ciks").and_then(|v| v.as_array()) {
+            let ciks: Vec<String> = ciks
+                .iter()
+                .filter_map(|c| c.as_str().map(str::to_string).or_else(|| c.as_u64().map(|n| n.to_string())))
+                .filter_map(|c| normalize_cik(&c))
+                .collect();
+            if ciks.is_empty() {
+                return Err(anyhow!("meta.ciks must list numeric CIKs"));
+            }
+            return Ok(EdgarQuery::Companies { ciks, forms });
+        }
+
+        if let Some(query) = meta.get("query").and_then(|v| v.as_str()).map(str::trim).filter(|q| !q.is_empty()) {
+            return Ok(EdgarQuery::Search {
+                query: query.to_string(),
+                forms,
+                days: meta.get("days").and_then(|v| v.as_i64()).unwrap_or(DEFAULT_SEARCH_DAYS),
+            });
+        }
+
+        Err(anyhow!("meta.ciks or meta.query is required"))
+    }
+}
+
+/// Zero-padded 10-digit CIK, as used in submissions URLs
+fn normalize_cik(cik: &str) -> Option<String> {
+    let digits = cik.trim().trim_start_matches("CIK");
+    if digits.is_empty() || digits.len() > 10 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{:0>10}", digits))
+}
+
+/// One filing, from either endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filing {
+    pub cik: String,
+    pub company: String,
+    pub ticker: Option<String>,
+    pub form: String,
+    pub accession_number: String,
+    pub filing_date: String,
+    pub report_date: Option<String>,
+    pub primary_document: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Filing {
+    /// The primary document when known, otherwise the filing index
+    fn url(&self) -> String {
+        let cik = self.cik.trim_start_matches('0');
+        let folder = self.accession_number.replace('-', "");
+        match &self.primary_document {
+            Some(document) => format!("{}/{}/{}/{}", ARCHIVES_URL, cik, folder, document),
+            None => format!("{}/{}/{}/{}-index.htm", ARCHIVES_URL, cik, folder, self.accession_number),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Submissions {
+    name: String,
+    #[serde(default)]
+    tickers: Vec<String>,
+    filings: SubmissionFilings,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmissionFilings {
+    recent: RecentFilings,
+}
+
+/// Column-oriented: the nth entry of each list describes the nth filing
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentFilings {
+    accession_number: Vec<String>,
+    filing_date: Vec<String>,
+    #[serde(default)]
+    report_date: Vec<String>,
+    form: Vec<String>,
+    #[serde(default)]
+    primary_document: Vec<String>,
+    #[serde(default)]
+    primary_doc_description: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    hits: SearchHits,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHits {
+    total: Option<SearchTotal>,
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchTotal {
+    value: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    /// `<accession number>:<document name>`
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(rename = "_source")]
+    source: SearchSource,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchSource {
+    #[serde(default)]
+    ciks: Vec<String>,
+    /// e.g. `NVIDIA CORP  (NVDA)  (CIK 0001045810)`
+    #[serde(default)]
+    display_names: Vec<String>,
+    form: Option<String>,
+    file_date: Option<String>,
+    period_ending: Option<String>,
+    file_description: Option<String>,
+}
+
+pub async fn run_edgar_ingestion(pool: &PgPool, source: &crate::models::Source, config: &Config) -> Result<u64> {
+    let query = EdgarQuery::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid edgar source {}: {}", source.name, e))?;
+
+    let user_agent = config
+        .sec_user_agent
+        .clone()
+        .ok_or_else(|| anyhow!("SEC_USER_AGENT environment variable not set (the SEC requires a contact User-Agent)"))?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(user_agent)
+        .build()?;
+
+    log::info!("Starting EDGAR ingestion for source: {} ({:?})", source.name, query);
+
+    let filings = match &query {
+        EdgarQuery::Companies { ciks, forms } => {
+            let mut filings = Vec::new();
+            for cik in ciks {
+                match fetch_company_filings(&client, cik, forms).await {
+                    Ok(company_filings) => filings.extend(company_filings),
+                    Err(e) => log::warn!("Failed to fetch EDGAR filings for CIK {}: {}", cik, e),
+                }
+                tokio::time::sleep(REQUEST_INTERVAL).await;
+            }
+            filings
+        }
+        EdgarQuery::Search { query, forms, days } => search_filings(&client, SEARCH_URL, query, forms, *days).await?,
+    };
+
+    let items: Vec<Item> = filings.iter().map(|filing| filing_to_item(filing, source)).collect();
+
+    log::info!("Fetched {} filings for source: {}", items.len(), source.name);
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+async fn fetch_company_filings(client: &reqwest::Client, cik: &str, forms: &[String]) -> Result<Vec<Filing>> {
+    let submissions: Submissions = client
+        .get(format!("{}/CIK{}.json", SUBMISSIONS_URL, cik))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(recent_filings(cik, submissions, forms))
+}
+
+fn recent_filings(cik: &str, submissions: Submissions, forms: &[String]) -> Vec<Filing> {
+    let recent = submissions.filings.recent;
+    let column = |values: &[String], i: usize| values.get(i).cloned().filter(|v| !v.is_empty());
+
+    (0..recent.accession_number.len())
+        .filter(|&i| forms.is_empty() || recent.form.get(i).map(|f| forms.contains(f)).unwrap_or(false))
+        .take(FILINGS_PER_COMPANY)
+        .map(|i| Filing {
+            cik: cik.to_string(),
+            company: submissions.name.clone(),
+            ticker: submissions.tickers.first().cloned(),
+            form: recent.form.get(i).cloned().unwrap_or_default(),
+            accession_number: recent.accession_number[i].clone(),
+            filing_date: recent.filing_date.get(i).cloned().unwrap_or_default(),
+            report_date: column(&recent.report_date, i),
+            primary_document: column(&recent.primary_document, i),
+            description: column(&recent.primary_doc_description, i),
+        })
+        .collect()
+}
+
+async fn search_filings(
+    client: &reqwest::Client,
+    search_url: &str,
+    query: &str,
+    forms: &[String],
+    days: i64,
+) -> Result<Vec<Filing>> {
+    let end = Utc::now().date_naive();
+    let start = end - chrono::Duration::days(days);
+
+    let mut params = vec![
+        ("q", query.to_string()),
+        ("dateRange", "custom".to_string()),
+        ("startdt", start.format("%Y-%m-%d").to_string()),
+        ("enddt", end.format("%Y-%m-%d").to_string()),
+    ];
+    if !forms.is_empty() {
+        params.push(("forms", forms.join(",")));
+    }
+
+    let mut filings = Vec::new();
+    let mut offset = 0;
+    for page in 0..MAX_SEARCH_PAGES {
+        if page > 0 {
+            tokio::time::sleep(REQUEST_INTERVAL).await;
+        }
+        let response: SearchResponse = client
+            .get(search_url)
+            .query(&params)
+            .query(&[("from", offset)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let hits = response.hits.hits.len();
+        offset += hits;
+        filings.extend(response.hits.hits.into_iter().filter_map(search_hit_to_filing));
+
+        let last_page = match response.hits.total {
+            Some(total) => offset >= total.value,
+            None => hits < SEARCH_PAGE_SIZE,
+        };
+        if hits == 0 || last_page {
+            break;
+        }
+    }
+
+    Ok(filings)
+}
+
+fn search_hit_to_filing(hit: SearchHit) -> Option<Filing> {
+    let (accession_number, document) = hit.id.split_once(':')?;
+    let display_name = hit.source.display_names.first().cloned().unwrap_or_default();
+
+    // "NVIDIA CORP  (NVDA)  (CIK 0001045810)" -> company and ticker
+    let company = display_name.split("  (").next().unwrap_or_default().trim().to_string();
+    let ticker = display_name
+        .split("  (")
+        .nth(1)
+        .map(|t| t.trim_end_matches(')').to_string())
+        .filter(|t| !t.starts_with("CIK"));
+
+    Some(Filing {
+        cik: normalize_cik(hit.source.ciks.first()?)?,
+        company,
+        ticker,
+        form: hit.source.form.unwrap_or_default(),
+        accession_number: accession_number.to_string(),
+        filing_date: hit.source.file_date.unwrap_or_default(),
+        report_date: hit.source.period_ending,
+        primary_document: Some(document.to_string()),
+        description: hit.source.file_description,
+    })
+}
+
+fn filing_to_item(filing: &Filing, source: &crate::models::Source) -> Item {
+    let title = match &filing.ticker {
+        Some(ticker) => format!("{} ({}) {} filed {}", filing.company, ticker, filing.form, filing.filing_date),
+        None => format!("{} {} filed {}", filing.company, filing.form, filing.filing_date),
+    };
+
+    let metadata = serde_json::json!({
+        "cik": filing.cik,
+        "company": filing.company,
+        "ticker": filing.ticker,
+        "form_type": filing.form,
+        "filing_date": filing.filing_date,
+        "report_date": filing.report_date,
+        "accession_number": filing.accession_number,
+    });
+
+    let published_at = NaiveDate::parse_from_str(&filing.filing_date, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| Utc.from_utc_datetime(&dt))
+        .unwrap_or_else(Utc::now);
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(filing.accession_number.clone()),
+        title,
+        url: filing.url(),
+        summary: Some(filing_summary(filing)),
+        body: None,
+        published_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+/// Readable one-liner, since filing descriptions are often just the form type again
+fn filing_summary(filing: &Filing) -> String {
+    let kind = match filing.form.as_str() {
+        "10-K" => "Annual report",
+        "10-Q" => "Quarterly report",
+        "8-K" => "Current report",
+        "20-F" => "Annual report (foreign issuer)",
+        "S-1" => "Registration statement",
+        "DEF 14A" => "Proxy statement",
+        _ => "Filing",
+    };
+    let mut summary = format!("{} ({}) filed with the SEC by {}", kind, filing.form, filing.company);
+    if let Some(period) = &filing.report_date {
+        summary.push_str(&format!(" for the period ending {}", period));
+    }
+    if let Some(description) = filing.description.as_deref().filter(|d| !d.eq_ignore_ascii_case(&filing.form)) {
+        summary.push_str(&format!(": {}", description));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> crate::models::Source {
+        crate::models::Source::test("edgar", "newsletter", serde_json::json!({ "ciks": ["1045810"] }))
+    }
+
+    #[test]
+    fn test_recent_filings_to_items() {
+        let submissions: Submissions = serde_json::from_value(serde_json::json!({
+            "cik": "1045810",
+            "name": "NVIDIA CORP",
+            "tickers": ["NVDA"],
+            "filings": { "recent": {
+                "accessionNumber": ["0001045810-26-000010", "0001045810-26-000009"],
+                "filingDate": ["2026-02-26", "2026-02-20"],
+                "reportDate": ["2026-01-25", ""],
+                "form": ["10-K", "4"],
+                "primaryDocument": ["nvda-20260125.htm", "xslF345X05/wk-form4.xml"],
+                "primaryDocDescription": ["10-K", "FORM 4"]
+            } }
+        }))
+        .unwrap();
+
+        let filings = recent_filings("0001045810", submissions, &["10-K".to_string()]);
+        assert_eq!(filings.len(), 1);
+
+        let item = filing_to_item(&filings[0], &source());
+        assert_eq!(item.title, "NVIDIA CORP (NVDA) 10-K filed 2026-02-26");
+        assert_eq!(
+            item.url,
+            "https://www.sec.gov/Archives/edgar/data/1045810/000104581026000010/nvda-20260125.htm"
+        );
+        assert_eq!(item.external_id.as_deref(), Some("0001045810-26-000010"));
+        assert_eq!(item.raw_metadata["form_type"], "10-K");
+        assert_eq!(item.raw_metadata["ticker"], "NVDA");
+        assert_eq!(
+            item.summary.as_deref(),
+            Some("Annual report (10-K) filed with the SEC by NVIDIA CORP for the period ending 2026-01-25")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_filings_pages_through_hits() {
+        use axum::extract::Query;
+        use axum::routing::get;
+        use axum::{Json, Router};
+        use std::collections::HashMap;
+
+        // 250 hits: two full pages and a partial one
+        async fn search(Query(params): Query<HashMap<String, String>>) -> Json<serde_json::Value> {
+            let from: usize = params.get("from").and_then(|from| from.parse().ok()).unwrap_or(0);
+            let hits: Vec<_> = (from..(from + SEARCH_PAGE_SIZE).min(250))
+                .map(|n| serde_json::json!({
+                    "_id": format!("0000320193-25-{:06}:doc{}.htm", n, n),
+                    "_source": { "ciks": ["0000320193"], "display_names": ["Apple Inc.  (AAPL)  (CIK 0000320193)"], "form": "8-K" }
+                }))
+                .collect();
+            Json(serde_json::json!({ "hits": { "total": { "value": 250 }, "hits": hits } }))
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let search_url = format!("http://{}/search-index", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, Router::new().route("/search-index", get(search))).await.unwrap() });
+
+        let filings = search_filings(&reqwest::Client::new(), &search_url, "\"language model\"", &[], 30).await.unwrap();
+        assert_eq!(filings.len(), 250);
+        assert_eq!(filings[249].accession_number, "0000320193-25-000249");
+    }
+
+    #[test]
+    fn test_search_hit_to_filing() {
+        let hit: SearchHit = serde_json::from_value(serde_json::json!({
+            "_id": "0000320193-25-000079:aapl-20250927.htm",
+            "_source": {
+                "ciks": ["0000320193"],
+                "display_names": ["Apple Inc.  (AAPL)  (CIK 0000320193)"],
+                "form": "10-K",
+                "file_date": "2025-10-31",
+                "period_ending": "2025-09-27"
+            }
+        }))
+        .unwrap();
+
+        let filing = search_hit_to_filing(hit).unwrap();
+        assert_eq!(filing.company, "Apple Inc.");
+        assert_eq!(filing.ticker.as_deref(), Some("AAPL"));
+        assert_eq!(filing.primary_document.as_deref(), Some("aapl-20250927.htm"));
+
+        assert_eq!(normalize_cik("320193").as_deref(), Some("0000320193"));
+        assert!(EdgarQuery::from_meta(&serde_json::json!({ "ciks": ["apple"] })).is_err());
+    }
+}
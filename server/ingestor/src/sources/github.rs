@@ -0,0 +1,391 @@
+//! GitHub ingestion for `github` sources: releases, an org's new repositories, or repository search
+//!
+//! `meta.mode` picks what to follow:
+//!   `{"mode": "releases", "repos": ["ggerganov/llama.cpp", "vllm-project/vllm"], "include_prereleases": false}`
+//!   `{"mode": "org_repos", "org": "huggingface"}`
+//!   `{"mode": "search", "query": "topic:llm stars:>500", "created_within_days": 7}`
+//! Requests use `GITHUB_TOKEN` when set (unauthenticated calls are limited to 60 per hour).
+//! `ingest_url` overrides the API root (default `https://api.github.com`).
+
+use crate::config::Config;
+use crate::html::truncate_chars;
+use crate::models::Item;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Releases fetched per repository, and repositories per org / search request
+const RELEASES_PER_REPO: &str = "10";
+const REPOS_PER_PAGE: &str = "50";
+
+/// What a source follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GithubFeed {
+    Releases { repos: Vec<String>, include_prereleases: bool },
+    OrgRepos { org: String },
+    Search { query: String },
+}
+
+impl GithubFeed {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| anyhow!("meta.{} is required", key))
+        };
+
+        match meta.get("mode").and_then(|v| v.as_str()) {
+            Some("releases") => {
+                let repos: Vec<String> = meta
+                    .get("repos")
+                    .and_then(|v| v.as_array())
+                    .map(|repos| {
+                        repos
+                            .iter()
+                            .filter_map(|r| r.as_str())
+                            .map(|r| r.trim().trim_matches('/').to_string())
+                            .filter(|r| r.contains('/'))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if repos.is_empty() {
+                    return Err(anyhow!("meta.repos must list owner/name repositories"));
+                }
+                Ok(GithubFeed::Releases {
+                    repos,
+                    include_prereleases: meta.get("include_prereleases").and_then(|v| v.as_bool()).unwrap_or(false),
+                })
+            }
+            Some("org_repos") => Ok(GithubFeed::OrgRepos { org: text("org")? }),
+            Some("search") => {
+                let mut query = text("query")?;
+                // "created last week" style searches
+                if let Some(days) = meta.get("created_within_days").and_then(|v| v.as_i64()) {
+                    let since = Utc::now() - chrono::Duration::days(days);
+                    query = format!("{} created:>={}", query, since.format("%Y-%m-%d"));
+                }
+                Ok(GithubFeed::Search { query })
+            }
+            Some(mode) => Err(anyhow!("Unknown github mode: {}", mode)),
+            None => Err(anyhow!("meta.mode is required")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Repository {
+    pub id: u64,
+    pub full_name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub stargazers_count: u64,
+    #[serde(default)]
+    pub forks_count: u64,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub license: Option<License>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct License {
+    pub spdx_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub html_url: String,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub author: Option<Author>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Author {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    items: Vec<Repository>,
+}
+
+pub struct GithubClient {
+    client: reqwest::Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GithubClient {
+    pub fn new(api_url: &str, token: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(crate::http::USER_AGENT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str, params: &[(&str, &str)]) -> Result<T> {
+        let mut request = self
+            .client
+            .get(format!("{}{}", self.api_url, path))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(params);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("GitHub API {} returned {}: {}", path, status, body.chars().take(200).collect::<String>()));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn repository(&self, repo: &str) -> Result<Repository> {
+        self.get(&format!("/repos/{}", repo), &[]).await
+    }
+
+    pub async fn releases(&self, repo: &str) -> Result<Vec<Release>> {
+        self.get(&format!("/repos/{}/releases", repo), &[("per_page", RELEASES_PER_REPO)])
+            .await
+    }
+
+    async fn repository_with_releases(&self, repo: &str) -> Result<(Repository, Vec<Release>)> {
+        Ok((self.repository(repo).await?, self.releases(repo).await?))
+    }
+
+    pub async fn org_repos(&self, org: &str) -> Result<Vec<Repository>> {
+        self.get(
+            &format!("/orgs/{}/repos", urlencoding::encode(org)),
+            &[("sort", "created"), ("direction", "desc"), ("per_page", REPOS_PER_PAGE)],
+        )
+        .await
+    }
+
+    pub async fn search_repos(&self, query: &str) -> Result<Vec<Repository>> {
+        let response: SearchResponse = self
+            .get(
+                "/search/repositories",
+                &[("q", query), ("sort", "stars"), ("order", "desc"), ("per_page", REPOS_PER_PAGE)],
+            )
+            .await?;
+        Ok(response.items)
+    }
+}
+
+pub async fn run_github_ingestion(pool: &PgPool, source: &crate::models::Source, config: &Config) -> Result<u64> {
+    let feed = GithubFeed::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid github source {}: {}", source.name, e))?;
+    let api_url = source
+        .ingest_url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or(DEFAULT_API_URL);
+
+    log::info!("Starting GitHub ingestion for source: {} ({:?})", source.name, feed);
+
+    let client = GithubClient::new(api_url, config.github_token.clone())?;
+    let items: Vec<Item> = match &feed {
+        GithubFeed::Releases { repos, include_prereleases } => {
+            let mut items = Vec::new();
+            for repo_name in repos {
+                // One failing repository (renamed, rate limited) shouldn't drop the others
+                match client.repository_with_releases(repo_name).await {
+                    Ok((repo, releases)) => items.extend(
+                        releases
+                            .iter()
+                            .filter(|release| !release.draft && (*include_prereleases || !release.prerelease))
+                            .map(|release| release_to_item(release, &repo, source)),
+                    ),
+                    Err(e) => log::warn!("Failed to fetch releases for {}: {}", repo_name, e),
+                }
+            }
+            items
+        }
+        GithubFeed::OrgRepos { org } => client
+            .org_repos(org)
+            .await?
+            .iter()
+            .filter(|repo| !repo.fork && !repo.archived)
+            .map(|repo| repo_to_item(repo, source))
+            .collect(),
+        GithubFeed::Search { query } => client
+            .search_repos(query)
+            .await?
+            .iter()
+            .map(|repo| repo_to_item(repo, source))
+            .collect(),
+    };
+
+    log::info!("Fetched {} items from GitHub for source: {}", items.len(), source.name);
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+fn repo_metadata(repo: &Repository) -> serde_json::Value {
+    serde_json::json!({
+        "repo": repo.full_name,
+        "stars": repo.stargazers_count,
+        "forks": repo.forks_count,
+        "language": repo.language,
+        "topics": repo.topics,
+        "license": repo.license.as_ref().and_then(|l| l.spdx_id.clone()),
+    })
+}
+
+fn release_to_item(release: &Release, repo: &Repository, source: &crate::models::Source) -> Item {
+    let release_name = release
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != release.tag_name);
+    let title = match release_name {
+        Some(name) => format!("{} {}: {}", repo.full_name, release.tag_name, name),
+        None => format!("{} {}", repo.full_name, release.tag_name),
+    };
+
+    let mut metadata = repo_metadata(repo);
+    if let Some(obj) = metadata.as_object_mut() {
+        obj.insert("release_tag".to_string(), serde_json::json!(release.tag_name));
+        obj.insert("release_name".to_string(), serde_json::json!(release_name));
+        obj.insert("prerelease".to_string(), serde_json::json!(release.prerelease));
+        obj.insert("author".to_string(), serde_json::json!(release.author.as_ref().map(|a| &a.login)));
+    }
+
+    // Release notes are Markdown; keep them as the summary text
+    let summary = release
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .map(|b| truncate_chars(b, 500));
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(format!("release:{}", release.id)),
+        title,
+        url: release.html_url.clone(),
+        summary,
+        body: None,
+        published_at: release.published_at.unwrap_or_else(Utc::now),
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn repo_to_item(repo: &Repository, source: &crate::models::Source) -> Item {
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(format!("repo:{}", repo.id)),
+        title: repo.full_name.clone(),
+        url: repo.html_url.clone(),
+        summary: repo.description.as_deref().map(str::trim).filter(|d| !d.is_empty()).map(str::to_string),
+        body: None,
+        published_at: repo.created_at,
+        raw_metadata: repo_metadata(repo),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> crate::models::Source {
+        crate::models::Source::test("github", "blog", serde_json::json!({ "mode": "releases", "repos": ["vllm-project/vllm"] }))
+    }
+
+    fn repo() -> Repository {
+        serde_json::from_value(serde_json::json!({
+            "id": 599547518,
+            "full_name": "vllm-project/vllm",
+            "html_url": "https://github.com/vllm-project/vllm",
+            "description": "A high-throughput and memory-efficient inference and serving engine for LLMs",
+            "stargazers_count": 40000,
+            "forks_count": 6000,
+            "language": "Python",
+            "topics": ["llm", "inference"],
+            "license": { "spdx_id": "Apache-2.0" },
+            "created_at": "2023-02-09T11:23:20Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_release_to_item() {
+        let release: Release = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "tag_name": "v0.9.0",
+            "name": "v0.9.0",
+            "html_url": "https://github.com/vllm-project/vllm/releases/tag/v0.9.0",
+            "body": "## Highlights\n- Faster sampling",
+            "prerelease": false,
+            "published_at": "2026-01-05T09:00:00Z",
+            "author": { "login": "maintainer" }
+        }))
+        .unwrap();
+
+        let item = release_to_item(&release, &repo(), &source());
+        assert_eq!(item.title, "vllm-project/vllm v0.9.0");
+        assert_eq!(item.external_id.as_deref(), Some("release:123"));
+        assert_eq!(item.raw_metadata["release_tag"], "v0.9.0");
+        assert_eq!(item.raw_metadata["stars"], 40000);
+        assert_eq!(item.raw_metadata["language"], "Python");
+
+        let item = repo_to_item(&repo(), &source());
+        assert_eq!(item.url, "https://github.com/vllm-project/vllm");
+        assert_eq!(item.raw_metadata["license"], "Apache-2.0");
+    }
+
+    #[test]
+    fn test_feed_from_meta() {
+        let feed = GithubFeed::from_meta(&serde_json::json!({
+            "mode": "search", "query": "topic:llm stars:>500", "created_within_days": 7
+        }))
+        .unwrap();
+        let GithubFeed::Search { query } = feed else { panic!("expected search") };
+        assert!(query.starts_with("topic:llm stars:>500 created:>="));
+
+        assert!(GithubFeed::from_meta(&serde_json::json!({ "mode": "releases", "repos": ["nonsense"] })).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+//! Hacker News ingestion for `hackernews` sources
+//!
+//! Options in `meta`:
+//!   `story_type`: `story` (default), `front_page`, `show_hn` or `ask_hn` — searched through Algolia
+//!   `list`: `top`, `best` or `new` — read from the Firebase API instead of searching
+//!   `query`: keyword the title must match, `min_points`: score threshold,
+//!   `window_hours`: how far back stories are (re)fetched so their points stay current (default 48)
+//! A story whose URL is already an item from another source gets the HN discussion attached to
+//! that item's `raw_metadata.hackernews` instead of becoming a duplicate item.
+
+use crate::html::{html_to_text, truncate_chars};
+use crate::models::Item;
+use crate::urls::canonicalize_url;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const ALGOLIA_API_URL: &str = "https://hn.algolia.com/api/v1";
+const FIREBASE_API_URL: &str = "https://hacker-news.firebaseio.com/v0";
+
+const DEFAULT_WINDOW_HOURS: i64 = 48;
+
+/// Stories taken from a Firebase list per cycle (each one is a separate request)
+const DEFAULT_LIST_LIMIT: usize = 30;
+
+/// What a source follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoryFilter {
+    pub story_type: String,
+    pub list: Option<String>,
+    pub query: Option<String>,
+    pub min_points: u64,
+    pub window_hours: i64,
+}
+
+impl StoryFilter {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        let story_type = text("story_type").unwrap_or_else(|| "story".to_string());
+        if !["story", "front_page", "show_hn", "ask_hn"].contains(&story_type.as_str()) {
+            return Err(anyhow!("Unknown story_type: {}", story_type));
+        }
+        let list = text("list");
+        if let Some(list) = &list {
+            if !["top", "best", "new"].contains(&list.as_str()) {
+                return Err(anyhow!("Unknown list: {}", list));
+            }
+        }
+
+        Ok(Self {
+            story_type,
+            list,
+            query: text("query"),
+            min_points: meta.get("min_points").and_then(|v| v.as_u64()).unwrap_or(0),
+            window_hours: meta
+                .get("window_hours")
+                .and_then(|v| v.as_i64())
+                .filter(|h| *h > 0)
+                .unwrap_or(DEFAULT_WINDOW_HOURS),
+        })
+    }
+
+    fn accepts(&self, story: &Story) -> bool {
+        let matches_query = self
+            .query
+            .as_ref()
+            .map(|query| story.title.to_lowercase().contains(&query.to_lowercase()))
+            .unwrap_or(true);
+        story.points >= self.min_points && matches_query
+    }
+}
+
+/// A story from either API
+#[derive(Debug, Clone, PartialEq)]
+pub struct Story {
+    pub id: u64,
+    pub title: String,
+    pub url: Option<String>,
+    pub author: Option<String>,
+    pub points: u64,
+    pub comments: u64,
+    pub created_at: DateTime<Utc>,
+    pub text: Option<String>,
+}
+
+impl Story {
+    pub fn discussion_url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.id)
+    }
+
+    /// What gets attached to (or stored on) an item
+    fn discussion_metadata(&self) -> serde_json::Value {
+        serde_json::json!({
+            "hn_id": self.id,
+            "points": self.points,
+            "comments": self.comments,
+            "discussion_url": self.discussion_url(),
+            "author": self.author,
+            "fetched_at": Utc::now(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AlgoliaResponse {
+    hits: Vec<AlgoliaHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlgoliaHit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+    title: Option<String>,
+    url: Option<String>,
+    author: Option<String>,
+    points: Option<u64>,
+    num_comments: Option<u64>,
+    created_at_i: i64,
+    story_text: Option<String>,
+}
+
+impl AlgoliaHit {
+    fn into_story(self) -> Option<Story> {
+        Some(Story {
+            id: self.object_id.parse().ok()?,
+            title: self.title?,
+            url: self.url.filter(|u| !u.is_empty()),
+            author: self.author,
+            points: self.points.unwrap_or(0),
+            comments: self.num_comments.unwrap_or(0),
+            created_at: DateTime::from_timestamp(self.created_at_i, 0)?,
+            text: self.story_text.filter(|t| !t.is_empty()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FirebaseItem {
+    id: u64,
+    #[serde(rename = "type")]
+    item_type: Option<String>,
+    title: Option<String>,
+    url: Option<String>,
+    by: Option<String>,
+    score: Option<u64>,
+    descendants: Option<u64>,
+    time: i64,
+    text: Option<String>,
+    #[serde(default)]
+    dead: bool,
+    #[serde(default)]
+    deleted: bool,
+}
+
+impl FirebaseItem {
+    fn into_story(self) -> Option<Story> {
+        if self.dead || self.deleted || self.item_type.as_deref() != Some("story") {
+            return None;
+        }
+        Some(Story {
+            id: self.id,
+            title: self.title?,
+            url: self.url.filter(|u| !u.is_empty()),
+            author: self.by,
+            points: self.score.unwrap_or(0),
+            comments: self.descendants.unwrap_or(0),
+            created_at: DateTime::from_timestamp(self.time, 0)?,
+            text: self.text.filter(|t| !t.is_empty()),
+        })
+    }
+}
+
+pub async fn run_hackernews_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let filter = StoryFilter::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid hackernews source {}: {}", source.name, e))?;
+
+    log::info!("Starting Hacker News ingestion for source: {} ({:?})", source.name, filter);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let stories = match &filter.list {
+        Some(list) => fetch_list(&client, list, DEFAULT_LIST_LIMIT).await?,
+        None => search_stories(&client, &filter).await?,
+    };
+    let stories: Vec<Story> = stories.into_iter().filter(|story| filter.accepts(story)).collect();
+
+    log::info!("Fetched {} matching stories for source: {}", stories.len(), source.name);
+
+    // Stories already tracked elsewhere get the discussion attached; the rest become items
+    let mut items = Vec::new();
+    let mut attached = 0;
+    for story in stories {
+        let item = story_to_item(&story, source);
+        if story.url.is_some() {
+            match crate::db::find_item_by_url_from_other_source(pool, source.id, &item.url).await {
+                Ok(Some(item_id)) => {
+                    match crate::db::set_item_metadata_key(pool, item_id, "hackernews", &story.discussion_metadata()).await {
+                        Ok(()) => attached += 1,
+                        Err(e) => log::warn!("Failed to attach HN discussion {} to {}: {}", story.id, item.url, e),
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to look up {}: {}", item.url, e),
+            }
+        }
+        items.push(item);
+    }
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!(
+        "Successfully inserted/updated {} items and attached {} discussions from source: {}",
+        inserted,
+        attached,
+        source.name
+    );
+
+    Ok(inserted + attached)
+}
+
+/// Recent stories from Algolia's `search_by_date`, filtered server-side by points and age
+async fn search_stories(client: &reqwest::Client, filter: &StoryFilter) -> Result<Vec<Story>> {
+    let since = Utc::now().timestamp() - filter.window_hours * 3600;
+    let numeric_filters = format!("created_at_i>{},points>={}", since, filter.min_points);
+    let tags = if filter.story_type == "story" {
+        "story".to_string()
+    } else {
+        format!("story,{}", filter.story_type)
+    };
+
+    let mut params = vec![
+        ("tags", tags.as_str()),
+        ("numericFilters", numeric_filters.as_str()),
+        ("hitsPerPage", "100"),
+    ];
+    if let Some(query) = &filter.query {
+        params.push(("query", query.as_str()));
+    }
+
+    let response: AlgoliaResponse = client
+        .get(format!("{}/search_by_date", ALGOLIA_API_URL))
+        .query(&params)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(response.hits.into_iter().filter_map(AlgoliaHit::into_story).collect())
+}
+
+/// The first `limit` stories of a Firebase list (`topstories`, `beststories`, `newstories`)
+async fn fetch_list(client: &reqwest::Client, list: &str, limit: usize) -> Result<Vec<Story>> {
+    let ids: Vec<u64> = client
+        .get(format!("{}/{}stories.json", FIREBASE_API_URL, list))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let mut stories = Vec::new();
+    for id in ids.into_iter().take(limit) {
+        let item = client
+            .get(format!("{}/item/{}.json", FIREBASE_API_URL, id))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match item {
+            Ok(response) => match response.json::<Option<FirebaseItem>>().await {
+                Ok(Some(item)) => stories.extend(item.into_story()),
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to parse HN item {}: {}", id, e),
+            },
+            Err(e) => log::warn!("Failed to fetch HN item {}: {}", id, e),
+        }
+    }
+
+    Ok(stories)
+}
+
+fn story_to_item(story: &Story, source: &crate::models::Source) -> Item {
+    // Ask HN and other text posts link to the discussion itself
+    let url = story
+        .url
+        .as_deref()
+        .map(canonicalize_url)
+        .unwrap_or_else(|| story.discussion_url());
+
+    let mut metadata = story.discussion_metadata();
+    if let Some(obj) = metadata.as_object_mut() {
+        obj.insert("story_url".to_string(), serde_json::json!(story.url));
+    }
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(story.id.to_string()),
+        title: story.title.trim().to_string(),
+        url,
+        summary: story
+            .text
+            .as_deref()
+            .map(html_to_text)
+            .filter(|t| !t.is_empty())
+            .map(|t| truncate_chars(&t, 500)),
+        body: None,
+        published_at: story.created_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_stories() {
+        let hit: AlgoliaHit = serde_json::from_value(serde_json::json!({
+            "objectID": "42000001",
+            "title": "Show HN: A tiny inference server",
+            "url": "https://example.com/post?utm_source=hn",
+            "author": "alice",
+            "points": 150,
+            "num_comments": 48,
+            "created_at_i": 1767603600,
+            "story_text": null
+        }))
+        .unwrap();
+        let story = hit.into_story().unwrap();
+        assert_eq!(story.points, 150);
+        assert_eq!(story.discussion_url(), "https://news.ycombinator.com/item?id=42000001");
+
+        let item: FirebaseItem = serde_json::from_value(serde_json::json!({
+            "id": 42000002, "type": "story", "title": "Ask HN: Favourite eval suites?",
+            "by": "bob", "score": 12, "descendants": 3, "time": 1767603600,
+            "text": "<p>Looking for <i>recommendations</i></p>"
+        }))
+        .unwrap();
+        let ask = item.into_story().unwrap();
+        assert!(ask.url.is_none());
+
+        let source = crate::models::Source::test("hackernews", "blog", serde_json::json!({}));
+        let item = story_to_item(&story, &source);
+        assert_eq!(item.url, "https://example.com/post");
+        assert_eq!(item.raw_metadata["comments"], 48);
+
+        let item = story_to_item(&ask, &source);
+        assert_eq!(item.url, "https://news.ycombinator.com/item?id=42000002");
+        assert_eq!(item.summary.as_deref(), Some("Looking for recommendations"));
+    }
+
+    #[test]
+    fn test_story_filter() {
+        let filter = StoryFilter::from_meta(&serde_json::json!({ "min_points": 100, "query": "LLM" })).unwrap();
+        let story = Story {
+            id: 1,
+            title: "Running an LLM on a phone".to_string(),
+            url: None,
+            author: None,
+            points: 120,
+            comments: 0,
+            created_at: Utc::now(),
+            text: None,
+        };
+        assert!(filter.accepts(&story));
+        assert!(!filter.accepts(&Story { points: 50, ..story.clone() }));
+        assert!(!filter.accepts(&Story { title: "Rust 2.0".to_string(), ..story }));
+        assert!(StoryFilter::from_meta(&serde_json::json!({ "list": "worst" })).is_err());
+    }
+}
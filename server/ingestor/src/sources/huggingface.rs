@@ -0,0 +1,428 @@
+//! Hugging Face Hub ingestion for `huggingface` sources: models, datasets and Daily Papers
+//!
+//! `meta.mode` picks the listing:
+//!   `{"mode": "models", "author": "meta-llama", "pipeline": "text-generation", "sort": "trending"}`
+//!   `{"mode": "datasets", "tag": "synthetic", "sort": "created"}`
+//!   `{"mode": "daily_papers"}`
+//! `sort` is `trending` (default), `created`, `downloads` or `likes`; `author`, `tag` and `pipeline`
+//! are optional filters. A Daily Papers entry whose arXiv paper is already an item from another
+//! source gets its upvotes attached to that item as `raw_metadata.huggingface` instead.
+//! `ingest_url` overrides the Hub root (default `https://huggingface.co`).
+
+use crate::html::truncate_chars;
+use crate::models::Item;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_BASE_URL: &str = "https://huggingface.co";
+
+/// Repositories / papers requested per listing
+const LISTING_LIMIT: &str = "50";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoKind {
+    Model,
+    Dataset,
+}
+
+impl RepoKind {
+    fn api_path(self) -> &'static str {
+        match self {
+            RepoKind::Model => "models",
+            RepoKind::Dataset => "datasets",
+        }
+    }
+
+    /// Web path prefix of a repository page (models live at the root)
+    fn web_prefix(self) -> &'static str {
+        match self {
+            RepoKind::Model => "",
+            RepoKind::Dataset => "datasets/",
+        }
+    }
+}
+
+/// What a source lists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HubListing {
+    Repos {
+        kind: RepoKind,
+        author: Option<String>,
+        tag: Option<String>,
+        pipeline: Option<String>,
+        sort: String,
+    },
+    DailyPapers,
+}
+
+impl HubListing {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        let kind = match meta.get("mode").and_then(|v| v.as_str()) {
+            Some("models") => RepoKind::Model,
+            Some("datasets") => RepoKind::Dataset,
+            Some("daily_papers") => return Ok(HubListing::DailyPapers),
+            Some(mode) => return Err(anyhow!("Unknown huggingface mode: {}", mode)),
+            None => return Err(anyhow!("meta.mode is required")),
+        };
+
+        let sort = text("sort").unwrap_or_else(|| "trending".to_string());
+        if !["trending", "created", "downloads", "likes"].contains(&sort.as_str()) {
+            return Err(anyhow!("Unknown sort: {}", sort));
+        }
+
+        Ok(HubListing::Repos {
+            kind,
+            author: text("author"),
+            tag: text("tag"),
+            pipeline: text("pipeline"),
+            sort,
+        })
+    }
+}
+
+/// A model or dataset as returned by `/api/models` and `/api/datasets`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HubRepo {
+    pub id: String,
+    #[serde(default)]
+    pub downloads: u64,
+    #[serde(default)]
+    pub likes: u64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(rename = "pipeline_tag")]
+    pub pipeline_tag: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub gated: serde_json::Value,
+}
+
+impl HubRepo {
+    /// The Hub exposes the license only as a `license:<id>` tag in listings
+    fn license(&self) -> Option<&str> {
+        self.tags.iter().find_map(|tag| tag.strip_prefix("license:"))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyPaper {
+    pub paper: Paper,
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub num_comments: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paper {
+    /// The arXiv id
+    pub id: String,
+    pub title: String,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<PaperAuthor>,
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub upvotes: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperAuthor {
+    pub name: String,
+}
+
+impl DailyPaper {
+    fn page_url(&self, base_url: &str) -> String {
+        format!("{}/papers/{}", base_url, self.paper.id)
+    }
+
+    /// What gets attached to an existing arXiv item
+    fn attachment_metadata(&self, base_url: &str) -> serde_json::Value {
+        serde_json::json!({
+            "paper_url": self.page_url(base_url),
+            "upvotes": self.paper.upvotes,
+            "comments": self.num_comments,
+        })
+    }
+}
+
+pub async fn run_huggingface_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let listing = HubListing::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid huggingface source {}: {}", source.name, e))?;
+    let base_url = source
+        .ingest_url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or(DEFAULT_BASE_URL)
+        .trim_end_matches('/')
+        .to_string();
+
+    log::info!("Starting Hugging Face ingestion for source: {} ({:?})", source.name, listing);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let (items, attached) = match &listing {
+        HubListing::Repos { kind, author, tag, pipeline, sort } => {
+            let repos = fetch_repos(&client, &base_url, *kind, author, tag, pipeline, sort).await?;
+            let items: Vec<Item> = repos
+                .iter()
+                .filter(|repo| !repo.private)
+                .map(|repo| repo_to_item(repo, *kind, &base_url, source))
+                .collect();
+            (items, 0)
+        }
+        HubListing::DailyPapers => {
+            let papers = fetch_daily_papers(&client, &base_url).await?;
+
+            // Papers already ingested from arXiv get the Hub discussion attached; the rest become items
+            let mut items = Vec::new();
+            let mut attached = 0;
+            for paper in papers {
+                match crate::db::find_arxiv_item(pool, source.id, &paper.paper.id).await {
+                    Ok(Some(item_id)) => {
+                        let metadata = paper.attachment_metadata(&base_url);
+                        match crate::db::set_item_metadata_key(pool, item_id, "huggingface", &metadata).await {
+                            Ok(()) => attached += 1,
+                            Err(e) => log::warn!("Failed to attach daily paper {}: {}", paper.paper.id, e),
+                        }
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::warn!("Failed to look up arXiv item {}: {}", paper.paper.id, e),
+                }
+                items.push(paper_to_item(&paper, &base_url, source));
+            }
+            (items, attached)
+        }
+    };
+
+    log::info!("Fetched {} items from the Hugging Face Hub for source: {}", items.len(), source.name);
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!(
+        "Successfully inserted/updated {} items and attached {} daily papers from source: {}",
+        inserted,
+        attached,
+        source.name
+    );
+
+    Ok(inserted + attached)
+}
+
+async fn fetch_repos(
+    client: &reqwest::Client,
+    base_url: &str,
+    kind: RepoKind,
+    author: &Option<String>,
+    tag: &Option<String>,
+    pipeline: &Option<String>,
+    sort: &str,
+) -> Result<Vec<HubRepo>> {
+    let sort_field = match sort {
+        "created" => "createdAt",
+        "downloads" => "downloads",
+        "likes" => "likes",
+        _ => "trendingScore",
+    };
+
+    let mut params: Vec<(&str, String)> = vec![
+        ("sort", sort_field.to_string()),
+        ("direction", "-1".to_string()),
+        ("limit", LISTING_LIMIT.to_string()),
+    ];
+    if let Some(author) = author {
+        params.push(("author", author.clone()));
+    }
+    if let Some(tag) = tag {
+        params.push(("filter", tag.clone()));
+    }
+    if let Some(pipeline) = pipeline {
+        // Datasets carry the task as a `task_categories:` tag rather than a pipeline tag
+        match kind {
+            RepoKind::Model => params.push(("pipeline_tag", pipeline.clone())),
+            RepoKind::Dataset => params.push(("filter", format!("task_categories:{}", pipeline))),
+        }
+    }
+
+    let repos = client
+        .get(format!("{}/api/{}", base_url, kind.api_path()))
+        .query(&params)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(repos)
+}
+
+async fn fetch_daily_papers(client: &reqwest::Client, base_url: &str) -> Result<Vec<DailyPaper>> {
+    let papers = client
+        .get(format!("{}/api/daily_papers", base_url))
+        .query(&[("limit", LISTING_LIMIT)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(papers)
+}
+
+fn repo_to_item(repo: &HubRepo, kind: RepoKind, base_url: &str, source: &crate::models::Source) -> Item {
+    // Skip the bookkeeping tags (license:, region:, arxiv:...) so `tags` stays readable
+    let tags: Vec<&String> = repo.tags.iter().filter(|tag| !tag.contains(':')).collect();
+
+    let metadata = serde_json::json!({
+        "hub_id": repo.id,
+        "repo_type": kind.api_path().trim_end_matches('s'),
+        "author": repo.id.split('/').next(),
+        "downloads": repo.downloads,
+        "likes": repo.likes,
+        "license": repo.license(),
+        "pipeline_tag": repo.pipeline_tag,
+        "tags": tags,
+        "gated": repo.gated.as_str().is_some() || repo.gated.as_bool().unwrap_or(false),
+    });
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(format!("{}:{}", kind.api_path(), repo.id)),
+        title: repo.id.clone(),
+        url: format!("{}/{}{}", base_url, kind.web_prefix(), repo.id),
+        summary: repo
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(|d| truncate_chars(d, 500)),
+        body: None,
+        published_at: repo.created_at.unwrap_or_else(Utc::now),
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn paper_to_item(paper: &DailyPaper, base_url: &str, source: &crate::models::Source) -> Item {
+    let authors: Vec<&str> = paper.paper.authors.iter().map(|a| a.name.as_str()).collect();
+
+    let metadata = serde_json::json!({
+        "arxiv_id": paper.paper.id,
+        "authors": authors,
+        "huggingface": paper.attachment_metadata(base_url),
+    });
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(paper.paper.id.clone()),
+        title: paper.paper.title.split_whitespace().collect::<Vec<_>>().join(" "),
+        url: format!("https://arxiv.org/abs/{}", paper.paper.id),
+        summary: paper.paper.summary.as_deref().map(|s| s.trim().to_string()),
+        body: None,
+        published_at: paper.published_at.or(paper.paper.published_at).unwrap_or_else(Utc::now),
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(medium: &str) -> crate::models::Source {
+        crate::models::Source::test("huggingface", medium, serde_json::json!({ "mode": "models" }))
+    }
+
+    #[test]
+    fn test_repo_to_item() {
+        let repo: HubRepo = serde_json::from_value(serde_json::json!({
+            "_id": "abc",
+            "id": "meta-llama/Llama-4-Scout",
+            "downloads": 120000,
+            "likes": 950,
+            "tags": ["transformers", "safetensors", "text-generation", "license:llama4", "region:us"],
+            "pipeline_tag": "text-generation",
+            "createdAt": "2026-01-05T09:00:00.000Z",
+            "private": false,
+            "gated": "manual"
+        }))
+        .unwrap();
+
+        let item = repo_to_item(&repo, RepoKind::Model, DEFAULT_BASE_URL, &source("blog"));
+        assert_eq!(item.url, "https://huggingface.co/meta-llama/Llama-4-Scout");
+        assert_eq!(item.external_id.as_deref(), Some("models:meta-llama/Llama-4-Scout"));
+        assert_eq!(item.raw_metadata["license"], "llama4");
+        assert_eq!(item.raw_metadata["author"], "meta-llama");
+        assert_eq!(item.raw_metadata["tags"], serde_json::json!(["transformers", "safetensors", "text-generation"]));
+        assert_eq!(item.raw_metadata["gated"], true);
+
+        let dataset = repo_to_item(&repo, RepoKind::Dataset, DEFAULT_BASE_URL, &source("blog"));
+        assert_eq!(dataset.url, "https://huggingface.co/datasets/meta-llama/Llama-4-Scout");
+    }
+
+    #[test]
+    fn test_paper_to_item() {
+        let paper: DailyPaper = serde_json::from_value(serde_json::json!({
+            "paper": {
+                "id": "2601.01234",
+                "title": "Scaling\n  Test-Time Compute",
+                "summary": "We study...",
+                "authors": [{ "_id": "1", "name": "Ada Lovelace" }],
+                "publishedAt": "2026-01-04T18:00:00.000Z",
+                "upvotes": 42
+            },
+            "publishedAt": "2026-01-05T09:00:00.000Z",
+            "numComments": 3
+        }))
+        .unwrap();
+
+        let item = paper_to_item(&paper, DEFAULT_BASE_URL, &source("paper"));
+        assert_eq!(item.title, "Scaling Test-Time Compute");
+        assert_eq!(item.url, "https://arxiv.org/abs/2601.01234");
+        assert_eq!(item.raw_metadata["arxiv_id"], "2601.01234");
+        assert_eq!(item.raw_metadata["huggingface"]["upvotes"], 42);
+        assert_eq!(item.raw_metadata["huggingface"]["paper_url"], "https://huggingface.co/papers/2601.01234");
+    }
+
+    #[test]
+    fn test_listing_from_meta() {
+        assert_eq!(
+            HubListing::from_meta(&serde_json::json!({ "mode": "daily_papers" })).unwrap(),
+            HubListing::DailyPapers
+        );
+        let HubListing::Repos { kind, sort, .. } =
+            HubListing::from_meta(&serde_json::json!({ "mode": "datasets", "author": "allenai" })).unwrap()
+        else {
+            panic!("expected repos")
+        };
+        assert_eq!(kind, RepoKind::Dataset);
+        assert_eq!(sort, "trending");
+        assert!(HubListing::from_meta(&serde_json::json!({ "mode": "models", "sort": "newest" })).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! One-off links for `manual` sources: `ingestor submit <url>` and `POST /submit`
+//!
+//! The page is fetched once and turned into an item from its title, description, author,
+//! publish date and OpenGraph image, with the main article text as the body. A URL (or the
+//! page's canonical URL) already stored by any source is not added again; the existing item
+//! is returned instead. Hosts on loopback, private or link-local addresses are never fetched.
+
+use crate::html::truncate_chars;
+use crate::models::{Item, Source};
+use crate::pages::{extract_page_metadata, PageMetadata};
+use crate::urls::canonicalize_url;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::PgPool;
+use url::Url;
+use uuid::Uuid;
+
+/// Outcome of a submission
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Submission {
+    pub item_id: Uuid,
+    pub url: String,
+    pub title: String,
+    /// The URL was already stored; nothing was written
+    pub duplicate: bool,
+}
+
+pub async fn submit_url(pool: &PgPool, source: &Source, url: &str) -> Result<Submission> {
+    if source.source_type != "manual" {
+        return Err(anyhow!(
+            "Source #{} is a {} source; links can only be submitted to manual sources",
+            source.id,
+            source.source_type
+        ));
+    }
+
+    let parsed = Url::parse(url.trim()).map_err(|e| anyhow!("Invalid URL {}: {}", url, e))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(anyhow!("Only http(s) URLs can be submitted"));
+    }
+    let canonical = canonicalize_url(parsed.as_str());
+
+    if let Some(existing) = find_existing(pool, source, &canonical).await? {
+        log::info!("Submitted URL {} is already stored as item {}", canonical, existing);
+        return Ok(Submission {
+            item_id: existing,
+            url: canonical,
+            title: String::new(),
+            duplicate: true,
+        });
+    }
+
+    let page = crate::http::shared().get_public_text(parsed.as_str()).await?;
+    let metadata = extract_page_metadata(&page, &parsed);
+
+    // The same article is often submitted under a different URL than the one stored
+    if let Some(page_canonical) = metadata.canonical_url.as_deref().map(canonicalize_url) {
+        if page_canonical != canonical {
+            if let Some(existing) = find_existing(pool, source, &page_canonical).await? {
+                log::info!("Submitted URL {} is already stored as item {} ({})", canonical, existing, page_canonical);
+                return Ok(Submission {
+                    item_id: existing,
+                    url: page_canonical,
+                    title: metadata.title.clone().unwrap_or_default(),
+                    duplicate: true,
+                });
+            }
+        }
+    }
+    let item = page_to_item(&canonical, &page, &metadata, source);
+    let title = item.title.clone();
+
+    // store_items runs topic extraction like every other source
+    if super::store_items(pool, source, vec![item]).await == 0 {
+        return Err(anyhow!("Failed to store {}", canonical));
+    }
+    let item_id = crate::db::find_existing_item_id(pool, source.id, None, &canonical)
+        .await?
+        .ok_or_else(|| anyhow!("Stored item for {} not found", canonical))?;
+
+    log::info!("Submitted {} to source {} as item {}", canonical, source.name, item_id);
+
+    Ok(Submission {
+        item_id,
+        url: canonical,
+        title,
+        duplicate: false,
+    })
+}
+
+/// An item with this URL in the manual source itself or in any other source
+async fn find_existing(pool: &PgPool, source: &Source, url: &str) -> Result<Option<Uuid>> {
+    if let Some(item_id) = crate::db::find_existing_item_id(pool, source.id, None, url).await? {
+        return Ok(Some(item_id));
+    }
+    crate::db::find_item_by_url_from_other_source(pool, source.id, url).await
+}
+
+fn page_to_item(url: &str, page: &str, metadata: &PageMetadata, source: &Source) -> Item {
+    let article = crate::fulltext::extract_main_content(page);
+
+    let mut raw_metadata = metadata.to_json();
+    if let Some(obj) = raw_metadata.as_object_mut() {
+        obj.insert("submitted_at".to_string(), serde_json::json!(Utc::now().to_rfc3339()));
+    }
+
+    // Pages without a description get the start of the article text
+    let summary = metadata
+        .description
+        .clone()
+        .or_else(|| article.as_ref().map(|a| a.text.clone()))
+        .filter(|s| !s.is_empty())
+        .map(|s| truncate_chars(&s, 500));
+
+    let mut item = Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: None,
+        title: metadata.title.clone().unwrap_or_else(|| url.to_string()),
+        url: url.to_string(),
+        summary,
+        body: None,
+        published_at: metadata.published_at.unwrap_or_else(Utc::now),
+        raw_metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    if let Some(article) = &article {
+        crate::fulltext::apply_article(&mut item, article);
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_to_item() {
+        let source = Source::test("manual", "blog", serde_json::json!({}));
+        let page = r#"<html><head>
+            <meta property="og:title" content="Why evals matter">
+            <meta property="og:image" content="https://example.com/cover.png">
+            <meta name="author" content="Ada Lovelace">
+        </head><body><article>
+            <p>Evaluations are how we learn whether a model improved, and they deserve real engineering time.</p>
+            <p>This post walks through building an eval suite, from task selection to grading, step by step.</p>
+        </article></body></html>"#;
+        let base = Url::parse("https://example.com/evals").unwrap();
+        let metadata = extract_page_metadata(page, &base);
+
+        let item = page_to_item("https://example.com/evals", page, &metadata, &source);
+        assert_eq!(item.title, "Why evals matter");
+        assert_eq!(item.raw_metadata["author"], "Ada Lovelace");
+        assert_eq!(item.raw_metadata["image_url"], "https://example.com/cover.png");
+        assert_eq!(item.raw_metadata["full_text"], true);
+        assert!(item.summary.unwrap().starts_with("Evaluations are how we learn"));
+        assert!(item.body.unwrap().contains("<p>"));
+    }
+}
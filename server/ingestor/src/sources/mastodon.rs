@@ -0,0 +1,518 @@
+//! Mastodon ingestion for `mastodon` sources, through the REST API or an ActivityPub outbox
+//!
+//! `ingest_url` is the instance (`https://sigmoid.social`) and `meta.mode` picks the timeline:
+//!   `{"mode": "account", "account": "someone"}`, `{"mode": "hashtag", "hashtag": "llm"}`
+//!   or `{"mode": "list", "list_id": "12"}` (needs `MASTODON_ACCESS_TOKEN`)
+//! With `{"mode": "outbox"}`, `ingest_url` is an actor (`https://instance/users/someone`) and its
+//! public outbox is read instead, which also works for non-Mastodon ActivityPub servers.
+
+use crate::config::Config;
+use crate::html::{html_to_text, truncate_chars};
+use crate::models::{Item, Medium};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Statuses per page (the API maximum)
+const PAGE_LIMIT: &str = "40";
+
+/// Pages fetched per cycle unless `meta.max_pages` says otherwise
+const DEFAULT_MAX_PAGES: u32 = 3;
+
+const ACTIVITY_JSON: &str = "application/activity+json";
+
+/// The timeline a source follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Timeline {
+    Account { acct: String },
+    Hashtag { tag: String },
+    List { list_id: String },
+    Outbox,
+}
+
+impl Timeline {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let field = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| anyhow!("meta.{} is required", key))
+        };
+
+        match meta.get("mode").and_then(|v| v.as_str()) {
+            Some("account") => Ok(Timeline::Account {
+                acct: field("account")?.trim_start_matches('@').to_string(),
+            }),
+            Some("hashtag") => Ok(Timeline::Hashtag {
+                tag: field("hashtag")?.trim_start_matches('#').to_string(),
+            }),
+            Some("list") => Ok(Timeline::List { list_id: field("list_id")? }),
+            Some("outbox") => Ok(Timeline::Outbox),
+            Some(mode) => Err(anyhow!("Unknown mastodon mode: {}", mode)),
+            None => Err(anyhow!("meta.mode is required")),
+        }
+    }
+}
+
+/// Sync position stored as `sources.meta.mastodon_state`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since_id: Option<String>,
+    /// Oldest status of a run cut short by `max_pages`; the next run pages on below it, and
+    /// `since_id` only moves on (to `pending_since_id`) once the backlog is drained
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_id: Option<String>,
+    /// Newest status fetched while a backlog is being paged through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending_since_id: Option<String>,
+    /// Resolved id of `meta.account` (looked up once)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    account_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    acct: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Status {
+    pub id: String,
+    pub uri: String,
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub spoiler_text: String,
+    #[serde(default)]
+    pub reblogs_count: u64,
+    #[serde(default)]
+    pub favourites_count: u64,
+    #[serde(default)]
+    pub replies_count: u64,
+    pub language: Option<String>,
+    pub in_reply_to_id: Option<String>,
+    pub account: Account,
+    pub reblog: Option<Box<Status>>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    pub card: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Account {
+    pub id: String,
+    pub acct: String,
+    #[serde(default)]
+    pub display_name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tag {
+    pub name: String,
+}
+
+pub async fn run_mastodon_ingestion(pool: &PgPool, source: &crate::models::Source, config: &Config) -> Result<u64> {
+    let base_url = match &source.ingest_url {
+        Some(url) => url.trim_end_matches('/').to_string(),
+        None => {
+            log::warn!("Mastodon source {} has no ingest_url, skipping", source.name);
+            return Ok(0);
+        }
+    };
+    let timeline = Timeline::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid mastodon source {}: {}", source.name, e))?;
+    let max_pages = source
+        .meta
+        .get("max_pages")
+        .and_then(|v| v.as_u64())
+        .map(|pages| pages.clamp(1, 20) as u32)
+        .unwrap_or(DEFAULT_MAX_PAGES);
+
+    let token = config.mastodon_access_token.as_deref();
+
+    log::info!("Starting Mastodon ingestion for source: {} ({:?})", source.name, timeline);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let items = if timeline == Timeline::Outbox {
+        fetch_outbox(&client, &base_url)
+            .await?
+            .iter()
+            .filter_map(|activity| note_to_item(activity, source))
+            .collect::<Vec<_>>()
+    } else {
+        let mut state: SyncState = source
+            .meta
+            .get("mastodon_state")
+            .and_then(|state| serde_json::from_value(state.clone()).ok())
+            .unwrap_or_default();
+
+        let path = match &timeline {
+            Timeline::Account { acct } => {
+                let account_id = match (&state.account_id, &state.acct) {
+                    (Some(id), Some(known)) if known.eq_ignore_ascii_case(acct) => id.clone(),
+                    _ => {
+                        let account: Account = get_json(&client, token, &format!("{}/api/v1/accounts/lookup", base_url), &[("acct", acct.as_str())]).await?;
+                        state.acct = Some(acct.clone());
+                        state.account_id = Some(account.id.clone());
+                        account.id
+                    }
+                };
+                format!("/api/v1/accounts/{}/statuses", account_id)
+            }
+            Timeline::Hashtag { tag } => format!("/api/v1/timelines/tag/{}", urlencoding::encode(tag)),
+            Timeline::List { list_id } => format!("/api/v1/timelines/list/{}", urlencoding::encode(list_id)),
+            Timeline::Outbox => return Err(anyhow!("Outbox sources are read over ActivityPub")),
+        };
+
+        let (statuses, max_id) = fetch_statuses(
+            &client,
+            token,
+            &base_url,
+            &path,
+            state.since_id.as_deref(),
+            state.max_id.as_deref(),
+            max_pages,
+        )
+        .await?;
+        // Statuses come newest first, and a backlog run only reaches further back
+        let newest = state.pending_since_id.take().or_else(|| statuses.first().map(|s| s.id.clone()));
+        if max_id.is_some() {
+            log::info!("Stopped after {} pages for source {}; the next run continues from there", max_pages, source.name);
+            state.pending_since_id = newest;
+        } else if newest.is_some() {
+            state.since_id = newest;
+        }
+        state.max_id = max_id;
+        if let Err(e) = crate::db::set_source_meta_key(pool, source.id, "mastodon_state", &serde_json::to_value(&state)?).await {
+            log::warn!("Failed to store Mastodon sync state for source {}: {}", source.name, e);
+        }
+
+        statuses.iter().map(|status| status_to_item(status, source)).collect()
+    };
+
+    log::info!("Fetched {} statuses for source: {}", items.len(), source.name);
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    token: Option<&str>,
+    url: &str,
+    params: &[(&str, &str)],
+) -> Result<T> {
+    let mut request = client.get(url).query(params);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+
+    Ok(request.send().await?.error_for_status()?.json().await?)
+}
+
+/// Statuses newer than `since_id` (and older than `max_id`, when resuming), newest first, paging
+/// back with `max_id`. Also returns the `max_id` to resume from when `max_pages` ran out first.
+async fn fetch_statuses(
+    client: &reqwest::Client,
+    token: Option<&str>,
+    base_url: &str,
+    path: &str,
+    since_id: Option<&str>,
+    max_id: Option<&str>,
+    max_pages: u32,
+) -> Result<(Vec<Status>, Option<String>)> {
+    let url = format!("{}{}", base_url, path);
+    let mut statuses: Vec<Status> = Vec::new();
+    let mut max_id = max_id.map(str::to_string);
+
+    for _ in 0..max_pages {
+        let mut params = vec![("limit", PAGE_LIMIT)];
+        if let Some(since_id) = since_id {
+            params.push(("since_id", since_id));
+        }
+        if let Some(max_id) = max_id.as_deref() {
+            params.push(("max_id", max_id));
+        }
+
+        let page: Vec<Status> = get_json(client, token, &url, &params).await?;
+        let full_page = page.len().to_string() == PAGE_LIMIT;
+        statuses.extend(page);
+        if !full_page {
+            return Ok((statuses, None));
+        }
+        max_id = statuses.last().map(|s| s.id.clone());
+    }
+
+    Ok((statuses, max_id))
+}
+
+/// `Create` activities from the first page of an actor's outbox
+async fn fetch_outbox(client: &reqwest::Client, actor_url: &str) -> Result<Vec<serde_json::Value>> {
+    let get = |url: String| async move {
+        client
+            .get(&url)
+            .header("Accept", ACTIVITY_JSON)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(anyhow::Error::from)
+    };
+
+    let actor = get(actor_url.to_string()).await?;
+    let outbox_url = actor
+        .get("outbox")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("Actor {} has no outbox", actor_url))?;
+
+    // The collection either embeds its first page or links to it
+    let outbox = get(outbox_url.to_string()).await?;
+    let page = match outbox.get("first") {
+        Some(serde_json::Value::String(first_url)) => get(first_url.clone()).await?,
+        Some(first) => first.clone(),
+        None => outbox,
+    };
+
+    Ok(page
+        .get("orderedItems")
+        .and_then(|items| items.as_array())
+        .cloned()
+        .unwrap_or_default())
+}
+
+fn status_to_item(status: &Status, source: &crate::models::Source) -> Item {
+    // A boost carries the original status; store that, noting who boosted it
+    let boosted_by = status.reblog.as_ref().map(|_| status.account.acct.clone());
+    let status = status.reblog.as_deref().unwrap_or(status);
+
+    let text = html_to_text(&status.content);
+    let title = item_title(&status.account.acct, &status.spoiler_text, &text);
+
+    let metadata = serde_json::json!({
+        "status_id": status.id,
+        "status_uri": status.uri,
+        "author_acct": status.account.acct,
+        "author_name": Some(status.account.display_name.clone()).filter(|n| !n.is_empty()),
+        "author_url": status.account.url,
+        "boosts": status.reblogs_count,
+        "favourites": status.favourites_count,
+        "replies": status.replies_count,
+        "in_reply_to_id": status.in_reply_to_id,
+        "boosted_by": boosted_by,
+        "language": status.language,
+        "tags": status.tags.iter().map(|t| &t.name).collect::<Vec<_>>(),
+        "card": status.card,
+    });
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: Medium::Tweet.as_str().to_string(),
+        external_id: Some(status.uri.clone()),
+        title,
+        url: status.url.clone().unwrap_or_else(|| status.uri.clone()),
+        summary: Some(text).filter(|t| !t.is_empty()),
+        body: None,
+        published_at: status.created_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+/// Map an outbox `Create` activity wrapping a public `Note`; boosts (`Announce`) only reference
+/// the original by id, so they are skipped
+fn note_to_item(activity: &serde_json::Value, source: &crate::models::Source) -> Option<Item> {
+    if activity.get("type")?.as_str()? != "Create" {
+        return None;
+    }
+    let note = activity.get("object")?;
+    if note.get("type")?.as_str()? != "Note" {
+        return None;
+    }
+
+    let uri = note.get("id")?.as_str()?.to_string();
+    let text = html_to_text(note.get("content").and_then(|c| c.as_str()).unwrap_or_default());
+    let summary_warning = note.get("summary").and_then(|s| s.as_str()).unwrap_or_default();
+    let author = note
+        .get("attributedTo")
+        .and_then(|a| a.as_str())
+        .unwrap_or_default();
+    let handle = author.rsplit('/').next().unwrap_or(author);
+    let title = item_title(handle, summary_warning, &text);
+
+    let published_at = note
+        .get("published")
+        .and_then(|p| p.as_str())
+        .and_then(|p| DateTime::parse_from_rfc3339(p).ok())
+        .map(|p| p.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    let total = |key: &str| note.get(key).and_then(|c| c.get("totalItems")).and_then(|n| n.as_u64());
+    let metadata = serde_json::json!({
+        "status_uri": uri,
+        "author_url": author,
+        "boosts": total("shares"),
+        "favourites": total("likes"),
+        "replies": total("replies"),
+        "in_reply_to": note.get("inReplyTo"),
+        "tags": note.get("tag")
+            .and_then(|tags| tags.as_array())
+            .map(|tags| tags
+                .iter()
+                .filter(|t| t.get("type").and_then(|t| t.as_str()) == Some("Hashtag"))
+                .filter_map(|t| t.get("name").and_then(|n| n.as_str()))
+                .map(|name| name.trim_start_matches('#').to_string())
+                .collect::<Vec<_>>())
+            .unwrap_or_default(),
+    });
+
+    Some(Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: Medium::Tweet.as_str().to_string(),
+        external_id: Some(uri.clone()),
+        title,
+        url: note
+            .get("url")
+            .and_then(|u| u.as_str())
+            .map(str::to_string)
+            .unwrap_or(uri),
+        summary: Some(text).filter(|t| !t.is_empty()),
+        body: None,
+        published_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    })
+}
+
+/// `@acct: first line`, using the content warning when there is one
+fn item_title(acct: &str, spoiler_text: &str, text: &str) -> String {
+    let headline = Some(spoiler_text.trim())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| text.lines().next().unwrap_or_default());
+    format!("@{}: {}", acct, truncate_chars(headline, 120))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> crate::models::Source {
+        crate::models::Source {
+            ingest_url: Some("https://sigmoid.social".to_string()),
+            ..crate::models::Source::test("mastodon", "tweet", serde_json::json!({ "mode": "hashtag", "hashtag": "llm" }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_statuses_resumes_where_max_pages_stopped() {
+        use axum::extract::Query;
+        use axum::routing::get;
+        use std::collections::HashMap;
+
+        // 50 statuses, newest first, filtered and paged like the real API
+        async fn statuses(Query(params): Query<HashMap<String, String>>) -> axum::Json<serde_json::Value> {
+            let id_param = |key: &str| params.get(key).and_then(|id| id.parse::<u64>().ok());
+            let (since_id, max_id) = (id_param("since_id").unwrap_or(0), id_param("max_id").unwrap_or(u64::MAX));
+            let page: Vec<_> = (1..=50u64)
+                .rev()
+                .filter(|id| *id > since_id && *id < max_id)
+                .take(PAGE_LIMIT.parse().unwrap())
+                .map(|id| serde_json::json!({
+                    "id": id.to_string(),
+                    "uri": format!("https://example.social/statuses/{}", id),
+                    "created_at": "2026-01-05T09:00:00Z",
+                    "account": { "id": "1", "acct": "example" },
+                }))
+                .collect();
+            axum::Json(serde_json::json!(page))
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let app = axum::Router::new().route("/api/v1/timelines/tag/llm", get(statuses));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let path = "/api/v1/timelines/tag/llm";
+        let (first, max_id) = fetch_statuses(&client, None, &base_url, path, Some("5"), None, 1).await.unwrap();
+        assert_eq!(first.len(), 40);
+        assert_eq!(max_id.as_deref(), Some("11"));
+
+        let (rest, max_id) = fetch_statuses(&client, None, &base_url, path, Some("5"), Some("11"), 1).await.unwrap();
+        assert_eq!(rest.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["10", "9", "8", "7", "6"]);
+        assert_eq!(max_id, None);
+    }
+
+    #[test]
+    fn test_boost_maps_to_original_status() {
+        let status: Status = serde_json::from_value(serde_json::json!({
+            "id": "200",
+            "uri": "https://sigmoid.social/users/booster/statuses/200/activity",
+            "url": null,
+            "created_at": "2026-01-05T10:00:00.000Z",
+            "content": "",
+            "account": { "id": "2", "acct": "booster", "display_name": "" },
+            "reblog": {
+                "id": "100",
+                "uri": "https://mastodon.example/users/alice/statuses/100",
+                "url": "https://mastodon.example/@alice/100",
+                "created_at": "2026-01-05T09:00:00.000Z",
+                "content": "<p>New paper on <a href=\"https://example.com/tags/llm\">#<span>llm</span></a> evals</p>",
+                "reblogs_count": 5,
+                "favourites_count": 12,
+                "account": { "id": "1", "acct": "alice@mastodon.example", "display_name": "Alice" },
+                "tags": [{ "name": "llm" }]
+            }
+        }))
+        .unwrap();
+
+        let item = status_to_item(&status, &source());
+        assert_eq!(item.title, "@alice@mastodon.example: New paper on #llm evals");
+        assert_eq!(item.external_id.as_deref(), Some("https://mastodon.example/users/alice/statuses/100"));
+        assert_eq!(item.url, "https://mastodon.example/@alice/100");
+        assert_eq!(item.source_type, "tweet");
+        assert_eq!(item.raw_metadata["boosts"], 5);
+        assert_eq!(item.raw_metadata["favourites"], 12);
+        assert_eq!(item.raw_metadata["boosted_by"], "booster");
+    }
+
+    #[test]
+    fn test_outbox_note_to_item() {
+        let activity = serde_json::json!({
+            "type": "Create",
+            "object": {
+                "type": "Note",
+                "id": "https://mastodon.example/users/alice/statuses/101",
+                "url": "https://mastodon.example/@alice/101",
+                "attributedTo": "https://mastodon.example/users/alice",
+                "published": "2026-01-06T08:00:00Z",
+                "content": "<p>Slides are up</p>",
+                "tag": [{ "type": "Hashtag", "name": "#talks" }]
+            }
+        });
+
+        let item = note_to_item(&activity, &source()).unwrap();
+        assert_eq!(item.title, "@alice: Slides are up");
+        assert_eq!(item.external_id.as_deref(), Some("https://mastodon.example/users/alice/statuses/101"));
+        assert_eq!(item.raw_metadata["tags"][0], "talks");
+
+        let boost = serde_json::json!({ "type": "Announce", "object": "https://elsewhere.example/notes/1" });
+        assert!(note_to_item(&boost, &source()).is_none());
+    }
+}
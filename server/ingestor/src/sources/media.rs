@@ -0,0 +1,236 @@
+//! Media attached to feed entries: enclosures, `media:` groups, iTunes and Podcasting 2.0 fields
+//! Stored as `raw_metadata.media` for podcast/video items
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+/// Per-item fields feed-rs doesn't expose (`itunes:episode`, `podcast:transcript`, ...)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PodcastExtras {
+    pub episode: Option<u32>,
+    pub season: Option<u32>,
+    pub episode_type: Option<String>,
+    pub transcripts: Vec<Transcript>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    pub url: String,
+    pub mime_type: Option<String>,
+    pub language: Option<String>,
+}
+
+/// Scan the raw feed for podcast extras, keyed by both the item's guid and its link
+pub fn parse_podcast_extras(raw_xml: &str) -> HashMap<String, PodcastExtras> {
+    let mut reader = Reader::from_str(raw_xml);
+    reader.trim_text(true);
+
+    let mut extras_by_key = HashMap::new();
+    let mut in_item = false;
+    let mut current_tag = String::new();
+    let mut extras = PodcastExtras::default();
+    let mut keys: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = tag_name(&e);
+                if name == "item" || name == "entry" {
+                    in_item = true;
+                    extras = PodcastExtras::default();
+                    keys.clear();
+                } else if in_item && name == "podcast:transcript" {
+                    extras.transcripts.extend(transcript_from(&e));
+                }
+                current_tag = name;
+            }
+            Ok(Event::Empty(e)) => {
+                let name = tag_name(&e);
+                if in_item && name == "podcast:transcript" {
+                    extras.transcripts.extend(transcript_from(&e));
+                }
+            }
+            Ok(Event::Text(text)) if in_item => {
+                let Ok(text) = text.unescape() else { continue };
+                let text = text.trim();
+                match current_tag.as_str() {
+                    "guid" | "link" | "id" => keys.push(text.to_string()),
+                    "itunes:episode" => extras.episode = text.parse().ok(),
+                    "itunes:season" => extras.season = text.parse().ok(),
+                    "itunes:episodeType" => extras.episode_type = Some(text.to_string()),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if in_item && (name == "item" || name == "entry") {
+                    in_item = false;
+                    if extras != PodcastExtras::default() {
+                        for key in keys.drain(..) {
+                            extras_by_key.insert(key, extras.clone());
+                        }
+                    }
+                }
+                current_tag.clear();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                log::debug!("Stopped scanning feed for podcast fields: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    extras_by_key
+}
+
+fn tag_name(e: &BytesStart) -> String {
+    String::from_utf8_lossy(e.name().as_ref()).to_string()
+}
+
+fn transcript_from(e: &BytesStart) -> Option<Transcript> {
+    let mut url = None;
+    let mut mime_type = None;
+    let mut language = None;
+    for attr in e.attributes().flatten() {
+        let value = attr.unescape_value().ok()?.to_string();
+        match attr.key.as_ref() {
+            b"url" => url = Some(value),
+            b"type" => mime_type = Some(value),
+            b"language" => language = Some(value),
+            _ => {}
+        }
+    }
+
+    Some(Transcript {
+        url: url?,
+        mime_type,
+        language,
+    })
+}
+
+/// Build `raw_metadata.media` from the entry's media objects and any podcast extras.
+/// Returns `None` when the entry carries no media at all.
+pub fn media_metadata(entry: &feed_rs::model::Entry, extras: Option<&PodcastExtras>) -> Option<serde_json::Value> {
+    let enclosures: Vec<serde_json::Value> = entry
+        .media
+        .iter()
+        .flat_map(|m| m.content.iter())
+        .filter_map(|c| {
+            let url = c.url.as_ref()?;
+            Some(serde_json::json!({
+                "url": url.as_str(),
+                "mime_type": c.content_type.as_ref().map(|t| t.to_string()),
+                "size_bytes": c.size,
+                "duration_secs": c.duration.map(|d| d.as_secs()),
+            }))
+        })
+        .collect();
+
+    let duration_secs = entry
+        .media
+        .iter()
+        .find_map(|m| m.duration.or_else(|| m.content.iter().find_map(|c| c.duration)))
+        .map(|d| d.as_secs());
+
+    let thumbnail_url = entry
+        .media
+        .iter()
+        .flat_map(|m| m.thumbnails.iter())
+        .map(|t| t.image.uri.clone())
+        .next();
+
+    let description = entry
+        .media
+        .iter()
+        .find_map(|m| m.description.as_ref())
+        .map(|d| crate::html::html_to_text(&d.content));
+
+    // media:community (YouTube puts view counts and star ratings here)
+    let community = entry.media.iter().find_map(|m| m.community.as_ref());
+    let views = community.and_then(|c| c.stats_views);
+    let rating = community.and_then(|c| c.stars_avg);
+
+    let mut transcripts: Vec<serde_json::Value> = entry
+        .media
+        .iter()
+        .flat_map(|m| m.texts.iter())
+        .map(|t| serde_json::json!({ "text": t.text.content }))
+        .collect();
+    if let Some(extras) = extras {
+        transcripts.extend(extras.transcripts.iter().map(|t| {
+            serde_json::json!({ "url": t.url, "mime_type": t.mime_type, "language": t.language })
+        }));
+    }
+
+    if enclosures.is_empty()
+        && thumbnail_url.is_none()
+        && description.is_none()
+        && views.is_none()
+        && transcripts.is_empty()
+        && extras.is_none()
+    {
+        return None;
+    }
+
+    Some(serde_json::json!({
+        "enclosures": enclosures,
+        "duration_secs": duration_secs,
+        "thumbnail_url": thumbnail_url,
+        "description": description,
+        "views": views,
+        "rating": rating,
+        "episode": extras.and_then(|e| e.episode),
+        "season": extras.and_then(|e| e.season),
+        "episode_type": extras.and_then(|e| e.episode_type.clone()),
+        "transcripts": transcripts,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PODCAST: &str = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+<channel>
+  <title>Latent Space</title>
+  <item>
+    <title>Episode 42</title>
+    <guid>ep-42</guid>
+    <link>https://example.com/ep42</link>
+    <enclosure url="https://cdn.example.com/ep42.mp3" type="audio/mpeg" length="1234"/>
+    <itunes:duration>01:02:03</itunes:duration>
+    <itunes:episode>42</itunes:episode>
+    <itunes:episodeType>full</itunes:episodeType>
+    <podcast:transcript url="https://example.com/ep42.vtt" type="text/vtt" language="en"/>
+  </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse_podcast_extras() {
+        let extras = parse_podcast_extras(PODCAST);
+        let ep = extras.get("ep-42").unwrap();
+        assert_eq!(ep.episode, Some(42));
+        assert_eq!(ep.episode_type.as_deref(), Some("full"));
+        assert_eq!(ep.transcripts[0].url, "https://example.com/ep42.vtt");
+        assert_eq!(extras.get("https://example.com/ep42"), Some(ep));
+    }
+
+    #[test]
+    fn test_media_metadata_from_enclosure() {
+        let feed = feed_rs::parser::parse(PODCAST.as_bytes()).unwrap();
+        let extras = parse_podcast_extras(PODCAST);
+        let entry = &feed.entries[0];
+
+        let media = media_metadata(entry, extras.get(&entry.id)).unwrap();
+        assert_eq!(media["enclosures"][0]["url"], "https://cdn.example.com/ep42.mp3");
+        assert_eq!(media["enclosures"][0]["mime_type"], "audio/mpeg");
+        assert_eq!(media["duration_secs"], 3723);
+        assert_eq!(media["episode"], 42);
+        assert_eq!(media["transcripts"][0]["language"], "en");
+    }
+}
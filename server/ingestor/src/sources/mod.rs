@@ -1,13 +1,72 @@
+pub mod archive;
 pub mod arxiv;
+pub mod biorxiv;
+pub mod bluesky;
+pub mod edgar;
+pub mod email;
+pub mod github;
+pub mod hackernews;
+pub mod huggingface;
+pub mod manual;
+pub mod mastodon;
+pub mod media;
+pub mod openreview;
+pub mod reddit;
 pub mod rss;
+pub mod scrape;
+pub mod sitemap;
+pub mod twitter;
+pub mod youtube;
 
 pub use arxiv::run_arxiv_ingestion;
+pub use biorxiv::run_biorxiv_ingestion;
+pub use bluesky::run_bluesky_ingestion;
+pub use edgar::run_edgar_ingestion;
+pub use email::run_email_ingestion;
+pub use github::run_github_ingestion;
+pub use hackernews::run_hackernews_ingestion;
+pub use huggingface::run_huggingface_ingestion;
+pub use mastodon::run_mastodon_ingestion;
+pub use openreview::run_openreview_ingestion;
+pub use reddit::run_reddit_ingestion;
 pub use rss::run_rss_ingestion;
+pub use scrape::run_scrape_ingestion;
+pub use sitemap::run_sitemap_ingestion;
+pub use twitter::run_twitter_ingestion;
+pub use youtube::run_youtube_ingestion;
 use anyhow::Result;
 use sqlx::PgPool;
 
+use crate::config::Config;
+use crate::models::{Item, Source};
+
+/// Insert or update items and tag them with topics. Returns how many were stored.
+pub async fn store_items(pool: &PgPool, source: &Source, items: Vec<Item>) -> u64 {
+    let mut inserted = 0;
+    for item in items {
+        match crate::db::insert_or_update_item(pool, &item).await {
+            Err(e) => {
+                log::warn!("Failed to insert item {} from {}: {}", item.url, source.name, e);
+            }
+            Ok(item_id) => {
+                inserted += 1;
+
+                // Extract and add topics in batch; the body covers full articles and transcripts
+                let body_text = item.body.as_deref().map(crate::html::html_to_text);
+                let topics = crate::topics::extract_topics(&item.title, item.summary.as_deref(), body_text.as_deref());
+                if !topics.is_empty() {
+                    if let Err(e) = crate::db::add_item_topics_batch(pool, item_id, &topics).await {
+                        log::warn!("Failed to add topics for item {}: {}", item.url, e);
+                    }
+                }
+            }
+        }
+    }
+    inserted
+}
+
 /// Generic ingestion dispatcher that routes to the appropriate ingestor based on source type
-pub async fn run_ingestion_cycle(pool: &PgPool) -> Result<u64> {
+pub async fn run_ingestion_cycle(pool: &PgPool, config: &Config) -> Result<u64> {
     log::info!("Starting ingestion cycle...");
 
     // Load all active sources from the database
@@ -29,12 +88,22 @@ pub async fn run_ingestion_cycle(pool: &PgPool) -> Result<u64> {
         let result = match source.source_type.as_str() {
             "arxiv" => run_arxiv_ingestion(pool, &source).await,
             "rss" => run_rss_ingestion(pool, &source).await,
-            "twitter_api" => {
-                log::info!("Twitter API ingestion not yet implemented for source: {}", source.name);
-                Ok(0)
-            },
+            "email" => run_email_ingestion(pool, &source).await,
+            "mastodon" => run_mastodon_ingestion(pool, &source, config).await,
+            "twitter_api" => run_twitter_ingestion(pool, &source, config).await,
+            "bluesky" => run_bluesky_ingestion(pool, &source).await,
+            "hackernews" => run_hackernews_ingestion(pool, &source).await,
+            "reddit" => run_reddit_ingestion(pool, &source).await,
+            "github" => run_github_ingestion(pool, &source, config).await,
+            "huggingface" => run_huggingface_ingestion(pool, &source).await,
+            "youtube" => run_youtube_ingestion(pool, &source).await,
+            "openreview" => run_openreview_ingestion(pool, &source).await,
+            "biorxiv" => run_biorxiv_ingestion(pool, &source).await,
+            "edgar" => run_edgar_ingestion(pool, &source, config).await,
+            "scrape" => run_scrape_ingestion(pool, &source).await,
+            "sitemap" => run_sitemap_ingestion(pool, &source).await,
             "manual" => {
-                log::info!("Manual source: {} - skipping automated ingestion", source.name);
+                log::info!("Manual source: {} - items arrive via `ingestor submit`/POST /submit", source.name);
                 Ok(0)
             },
             unknown => {
@@ -0,0 +1,374 @@
+//! OpenReview ingestion for `openreview` sources: conference submissions through the API v2
+//!
+//! Options in `meta`:
+//!   `venue_id` (required, e.g. `ICLR.cc/2026/Conference`), `accepted_only` (default false:
+//!   every submission, including ones still under review), `max_notes` (default 2000)
+//! Reviews and decisions come from each note's replies. Submissions whose title matches an
+//! item carrying an `arxiv_id` are cross-linked: the arXiv item gets `raw_metadata.openreview`
+//! and the submission gets `raw_metadata.arxiv`.
+//! `ingest_url` overrides the API root (default `https://api2.openreview.net`).
+
+use crate::html::truncate_chars;
+use crate::models::{Item, Medium};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_API_URL: &str = "https://api2.openreview.net";
+const FORUM_URL: &str = "https://openreview.net/forum";
+
+/// Notes per request (the API maximum)
+const PAGE_SIZE: usize = 1000;
+const DEFAULT_MAX_NOTES: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VenueOptions {
+    pub venue_id: String,
+    pub accepted_only: bool,
+    pub max_notes: usize,
+}
+
+impl VenueOptions {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let venue_id = meta
+            .get("venue_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().trim_end_matches('/').to_string())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("meta.venue_id is required"))?;
+
+        Ok(Self {
+            venue_id,
+            accepted_only: meta.get("accepted_only").and_then(|v| v.as_bool()).unwrap_or(false),
+            max_notes: meta
+                .get("max_notes")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize)
+                .unwrap_or(DEFAULT_MAX_NOTES),
+        })
+    }
+
+    /// Accepted papers carry the venue id itself; submissions are notes of the venue's Submission invitation
+    fn query(&self) -> (&'static str, String) {
+        if self.accepted_only {
+            ("content.venueid", self.venue_id.clone())
+        } else {
+            ("invitation", format!("{}/-/Submission", self.venue_id))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NotesResponse {
+    notes: Vec<Note>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Note {
+    pub id: String,
+    pub forum: String,
+    /// Milliseconds since the epoch
+    pub cdate: Option<i64>,
+    pub pdate: Option<i64>,
+    #[serde(default)]
+    pub content: HashMap<String, Field>,
+    pub details: Option<NoteDetails>,
+}
+
+/// API v2 wraps every content value as `{"value": ...}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Field {
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NoteDetails {
+    #[serde(default)]
+    pub replies: Vec<Reply>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Reply {
+    #[serde(default)]
+    pub invitations: Vec<String>,
+    #[serde(default)]
+    pub content: HashMap<String, Field>,
+}
+
+impl Note {
+    fn text(&self, key: &str) -> Option<String> {
+        self.content
+            .get(key)
+            .and_then(|f| f.value.as_str())
+            .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|s| !s.is_empty())
+    }
+
+    fn list(&self, key: &str) -> Vec<String> {
+        self.content
+            .get(key)
+            .and_then(|f| f.value.as_array())
+            .map(|values| values.iter().filter_map(|v| v.as_str()).map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    fn replies_of(&self, kind: &str) -> impl Iterator<Item = &Reply> {
+        let suffix = format!("/-/{}", kind);
+        self.details
+            .iter()
+            .flat_map(|d| d.replies.iter())
+            .filter(move |reply| reply.invitations.iter().any(|inv| inv.ends_with(&suffix)))
+    }
+
+    pub fn decision(&self) -> Option<String> {
+        self.replies_of("Decision")
+            .find_map(|reply| reply.content.get("decision"))
+            .and_then(|f| f.value.as_str())
+            .map(str::to_string)
+    }
+
+    /// Review scores; venues use either `rating` or `recommendation`, as a number or "8: accept, good paper"
+    pub fn ratings(&self) -> Vec<f64> {
+        self.replies_of("Official_Review")
+            .filter_map(|reply| reply.content.get("rating").or_else(|| reply.content.get("recommendation")))
+            .filter_map(|f| match &f.value {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => s.split(':').next().and_then(|n| n.trim().parse().ok()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn forum_url(&self) -> String {
+        format!("{}?id={}", FORUM_URL, self.forum)
+    }
+}
+
+pub async fn run_openreview_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let options = VenueOptions::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid openreview source {}: {}", source.name, e))?;
+    let api_url = source
+        .ingest_url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or(DEFAULT_API_URL)
+        .trim_end_matches('/')
+        .to_string();
+
+    log::info!("Starting OpenReview ingestion for source: {} ({})", source.name, options.venue_id);
+
+    let notes = fetch_notes(&api_url, &options).await?;
+    let mut items: Vec<Item> = notes.iter().filter_map(|note| note_to_item(note, &options, source)).collect();
+
+    log::info!("Fetched {} submissions from OpenReview for source: {}", items.len(), source.name);
+
+    let linked = link_arxiv_items(pool, &mut items).await;
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!(
+        "Successfully inserted/updated {} items ({} linked to arXiv) from source: {}",
+        inserted,
+        linked,
+        source.name
+    );
+
+    Ok(inserted)
+}
+
+async fn fetch_notes(api_url: &str, options: &VenueOptions) -> Result<Vec<Note>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let (filter_key, filter_value) = options.query();
+    let mut notes = Vec::new();
+    while notes.len() < options.max_notes {
+        let limit = PAGE_SIZE.min(options.max_notes - notes.len());
+        let response: NotesResponse = client
+            .get(format!("{}/notes", api_url))
+            .query(&[
+                (filter_key, filter_value.as_str()),
+                ("details", "replies"),
+                ("limit", &limit.to_string()),
+                ("offset", &notes.len().to_string()),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let page_len = response.notes.len();
+        notes.extend(response.notes);
+        if page_len < limit {
+            break;
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Normalised title used to match submissions against arXiv items
+fn normalize_title(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Cross-link submissions with arXiv items of the same title; returns how many were linked
+async fn link_arxiv_items(pool: &PgPool, items: &mut [Item]) -> u64 {
+    let titles: Vec<String> = items.iter().map(|item| normalize_title(&item.title)).collect();
+    let matches = match crate::db::find_arxiv_items_by_title(pool, &titles).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::warn!("Failed to look up arXiv items by title: {}", e);
+            return 0;
+        }
+    };
+
+    // Oldest match wins when several sources stored the same paper
+    let mut by_title: HashMap<String, (Uuid, String, String)> = HashMap::new();
+    for (item_id, title, arxiv_id, url) in matches {
+        by_title.entry(title).or_insert((item_id, arxiv_id, url));
+    }
+
+    let mut linked = 0;
+    for (item, title) in items.iter_mut().zip(titles) {
+        let Some((arxiv_item_id, arxiv_id, arxiv_url)) = by_title.get(&title) else {
+            continue;
+        };
+
+        let openreview = serde_json::json!({
+            "forum_url": item.url,
+            "venue": item.raw_metadata["venue"],
+            "decision": item.raw_metadata["decision"],
+            "rating_avg": item.raw_metadata["rating_avg"],
+        });
+        if let Err(e) = crate::db::set_item_metadata_key(pool, *arxiv_item_id, "openreview", &openreview).await {
+            log::warn!("Failed to link arXiv item {} to {}: {}", arxiv_id, item.url, e);
+            continue;
+        }
+
+        if let Some(obj) = item.raw_metadata.as_object_mut() {
+            obj.insert(
+                "arxiv".to_string(),
+                serde_json::json!({ "arxiv_id": arxiv_id, "url": arxiv_url, "item_id": arxiv_item_id }),
+            );
+        }
+        linked += 1;
+    }
+
+    linked
+}
+
+fn note_to_item(note: &Note, options: &VenueOptions, source: &crate::models::Source) -> Option<Item> {
+    let title = note.text("title")?;
+
+    let ratings = note.ratings();
+    let rating_avg = (!ratings.is_empty()).then(|| {
+        let avg = ratings.iter().sum::<f64>() / ratings.len() as f64;
+        (avg * 100.0).round() / 100.0
+    });
+    let pdf_url = note
+        .text("pdf")
+        .map(|pdf| if pdf.starts_with('/') { format!("https://openreview.net{}", pdf) } else { pdf });
+
+    let metadata = serde_json::json!({
+        "openreview_id": note.id,
+        "venue_id": options.venue_id,
+        "venue": note.text("venue"),
+        "authors": note.list("authors"),
+        "keywords": note.list("keywords"),
+        "primary_area": note.text("primary_area"),
+        "decision": note.decision(),
+        "ratings": ratings,
+        "rating_avg": rating_avg,
+        "pdf_url": pdf_url,
+    });
+
+    let published_at = note
+        .pdate
+        .or(note.cdate)
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+
+    Some(Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: Medium::Paper.as_str().to_string(),
+        external_id: Some(note.id.clone()),
+        title,
+        url: note.forum_url(),
+        summary: note.text("abstract").map(|a| truncate_chars(&a, 2000)),
+        body: None,
+        published_at,
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note() -> Note {
+        serde_json::from_value(serde_json::json!({
+            "id": "abc123",
+            "forum": "abc123",
+            "cdate": 1727740800000i64,
+            "content": {
+                "title": { "value": "Scaling  Test-Time\nCompute" },
+                "authors": { "value": ["Ada Lovelace", "Alan Turing"] },
+                "keywords": { "value": ["reasoning", "inference"] },
+                "abstract": { "value": "We study..." },
+                "venue": { "value": "ICLR 2026 Poster" },
+                "pdf": { "value": "/pdf/0123456789abcdef.pdf" }
+            },
+            "details": { "replies": [
+                { "invitations": ["ICLR.cc/2026/Conference/Submission42/-/Official_Review"],
+                  "content": { "rating": { "value": 8 } } },
+                { "invitations": ["ICLR.cc/2026/Conference/Submission42/-/Official_Review"],
+                  "content": { "rating": { "value": "5: marginally below the acceptance threshold" } } },
+                { "invitations": ["ICLR.cc/2026/Conference/Submission42/-/Official_Comment"],
+                  "content": { "comment": { "value": "Thanks!" } } },
+                { "invitations": ["ICLR.cc/2026/Conference/Submission42/-/Decision"],
+                  "content": { "decision": { "value": "Accept (poster)" } } }
+            ] }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_note_to_item() {
+        let source = crate::models::Source::test("openreview", "paper", serde_json::json!({ "venue_id": "ICLR.cc/2026/Conference" }));
+        let options = VenueOptions::from_meta(&source.meta).unwrap();
+
+        let item = note_to_item(&note(), &options, &source).unwrap();
+        assert_eq!(item.title, "Scaling Test-Time Compute");
+        assert_eq!(item.url, "https://openreview.net/forum?id=abc123");
+        assert_eq!(item.source_type, "paper");
+        assert_eq!(item.raw_metadata["decision"], "Accept (poster)");
+        assert_eq!(item.raw_metadata["ratings"], serde_json::json!([8.0, 5.0]));
+        assert_eq!(item.raw_metadata["rating_avg"], 6.5);
+        assert_eq!(item.raw_metadata["pdf_url"], "https://openreview.net/pdf/0123456789abcdef.pdf");
+        assert_eq!(normalize_title("Scaling  Test-Time\nCompute"), "scaling test-time compute");
+    }
+
+    #[test]
+    fn test_venue_query() {
+        let options = VenueOptions::from_meta(&serde_json::json!({ "venue_id": "ICLR.cc/2026/Conference/" })).unwrap();
+        assert_eq!(options.query(), ("invitation", "ICLR.cc/2026/Conference/-/Submission".to_string()));
+        assert_eq!(options.max_notes, DEFAULT_MAX_NOTES);
+
+        let accepted =
+            VenueOptions::from_meta(&serde_json::json!({ "venue_id": "NeurIPS.cc/2025/Conference", "accepted_only": true }))
+                .unwrap();
+        assert_eq!(accepted.query(), ("content.venueid", "NeurIPS.cc/2025/Conference".to_string()));
+        assert!(VenueOptions::from_meta(&serde_json::json!({})).is_err());
+    }
+}
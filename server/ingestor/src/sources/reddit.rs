@@ -0,0 +1,303 @@
+//! Reddit ingestion for `reddit` sources through the public JSON listings
+//!
+//! Options in `meta`:
+//!   `subreddit` (required), `listing`: `hot` (default), `top` or `new`,
+//!   `timeframe` for `top`: `hour`, `day`, `week` (default), `month`, `year` or `all`,
+//!   `min_score`, `flairs` (only these link flairs), `exclude_flairs`, `include_nsfw`
+//! Stickied posts are always skipped. `ingest_url` overrides the host (default `https://www.reddit.com`).
+
+use crate::html::{html_to_text, sanitize_html, truncate_chars};
+use crate::models::Item;
+use crate::urls::canonicalize_url;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_BASE_URL: &str = "https://www.reddit.com";
+
+/// Posts requested per listing (the API maximum)
+const LISTING_LIMIT: &str = "100";
+
+/// Which listing to read and which posts to keep
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingOptions {
+    pub subreddit: String,
+    pub listing: String,
+    pub timeframe: String,
+    pub min_score: i64,
+    pub flairs: Vec<String>,
+    pub exclude_flairs: Vec<String>,
+    pub include_nsfw: bool,
+}
+
+impl ListingOptions {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        let list = |key: &str| -> Vec<String> {
+            meta.get(key)
+                .and_then(|v| v.as_array())
+                .map(|values| values.iter().filter_map(|v| v.as_str()).map(|s| s.trim().to_lowercase()).collect())
+                .unwrap_or_default()
+        };
+
+        let subreddit = text("subreddit")
+            .ok_or_else(|| anyhow!("meta.subreddit is required"))?
+            .trim_start_matches("r/")
+            .trim_start_matches("/r/")
+            .to_string();
+        let listing = text("listing").unwrap_or_else(|| "hot".to_string());
+        if !["hot", "top", "new"].contains(&listing.as_str()) {
+            return Err(anyhow!("Unknown listing: {}", listing));
+        }
+        let timeframe = text("timeframe").unwrap_or_else(|| "week".to_string());
+        if !["hour", "day", "week", "month", "year", "all"].contains(&timeframe.as_str()) {
+            return Err(anyhow!("Unknown timeframe: {}", timeframe));
+        }
+
+        Ok(Self {
+            subreddit,
+            listing,
+            timeframe,
+            min_score: meta.get("min_score").and_then(|v| v.as_i64()).unwrap_or(0),
+            flairs: list("flairs"),
+            exclude_flairs: list("exclude_flairs"),
+            include_nsfw: meta.get("include_nsfw").and_then(|v| v.as_bool()).unwrap_or(false),
+        })
+    }
+
+    fn accepts(&self, post: &Post) -> bool {
+        let flair = post.link_flair_text.as_deref().map(|f| f.trim().to_lowercase());
+        let flair_allowed = self.flairs.is_empty() || flair.as_ref().map(|f| self.flairs.contains(f)).unwrap_or(false);
+        let flair_excluded = flair.as_ref().map(|f| self.exclude_flairs.contains(f)).unwrap_or(false);
+
+        !post.stickied
+            && (self.include_nsfw || !post.over_18)
+            && post.score >= self.min_score
+            && flair_allowed
+            && !flair_excluded
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Listing {
+    data: ListingData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingData {
+    children: Vec<Thing>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Thing {
+    data: Post,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Post {
+    pub name: String,
+    pub title: String,
+    pub url: Option<String>,
+    pub permalink: String,
+    #[serde(default)]
+    pub is_self: bool,
+    pub selftext_html: Option<String>,
+    #[serde(default)]
+    pub score: i64,
+    #[serde(default)]
+    pub num_comments: u64,
+    pub upvote_ratio: Option<f64>,
+    pub link_flair_text: Option<String>,
+    pub author: Option<String>,
+    pub subreddit: String,
+    pub domain: Option<String>,
+    pub created_utc: f64,
+    #[serde(default)]
+    pub stickied: bool,
+    #[serde(default)]
+    pub over_18: bool,
+}
+
+pub async fn run_reddit_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let options = ListingOptions::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid reddit source {}: {}", source.name, e))?;
+    let base_url = source
+        .ingest_url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or(DEFAULT_BASE_URL)
+        .trim_end_matches('/')
+        .to_string();
+
+    log::info!(
+        "Starting Reddit ingestion for source: {} (r/{} {})",
+        source.name,
+        options.subreddit,
+        options.listing
+    );
+
+    let posts = fetch_listing(&base_url, &options).await?;
+    let total = posts.len();
+    let items: Vec<Item> = posts
+        .iter()
+        .filter(|post| options.accepts(post))
+        .map(|post| post_to_item(post, &base_url, source))
+        .collect();
+
+    log::info!("Kept {} of {} posts for source: {}", items.len(), total, source.name);
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+async fn fetch_listing(base_url: &str, options: &ListingOptions) -> Result<Vec<Post>> {
+    // Reddit rejects generic user agents, so always send ours
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let mut params = vec![("limit", LISTING_LIMIT), ("raw_json", "1")];
+    if options.listing == "top" {
+        params.push(("t", options.timeframe.as_str()));
+    }
+
+    let listing: Listing = client
+        .get(format!("{}/r/{}/{}.json", base_url, urlencoding::encode(&options.subreddit), options.listing))
+        .query(&params)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(listing.data.children.into_iter().map(|thing| thing.data).collect())
+}
+
+fn post_to_item(post: &Post, base_url: &str, source: &crate::models::Source) -> Item {
+    let discussion_url = format!("{}{}", base_url, post.permalink);
+
+    // Self posts (and link posts pointing back at reddit) are the discussion itself
+    let url = match post.url.as_deref() {
+        Some(url) if !post.is_self && url.starts_with("http") => canonicalize_url(url),
+        _ => discussion_url.clone(),
+    };
+
+    let summary = post
+        .selftext_html
+        .as_deref()
+        .map(html_to_text)
+        .filter(|t| !t.is_empty())
+        .map(|t| truncate_chars(&t, 500));
+    let body = post
+        .selftext_html
+        .as_deref()
+        .map(|html| sanitize_html(&truncate_chars(html, 10000)))
+        .filter(|b| !b.trim().is_empty());
+
+    let metadata = serde_json::json!({
+        "reddit_id": post.name,
+        "subreddit": post.subreddit,
+        "score": post.score,
+        "comments": post.num_comments,
+        "upvote_ratio": post.upvote_ratio,
+        "flair": post.link_flair_text,
+        "author": post.author,
+        "is_self": post.is_self,
+        "domain": post.domain,
+        "discussion_url": discussion_url,
+    });
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: Some(post.name.clone()),
+        title: post.title.trim().to_string(),
+        url,
+        summary,
+        body,
+        published_at: DateTime::from_timestamp(post.created_utc as i64, 0).unwrap_or_else(Utc::now),
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn posts() -> Vec<Post> {
+        let listing: Listing = serde_json::from_value(serde_json::json!({
+            "kind": "Listing",
+            "data": { "children": [
+                { "kind": "t3", "data": {
+                    "name": "t3_abc", "title": "[R] New scaling paper", "url": "https://arxiv.org/abs/2601.00001",
+                    "permalink": "/r/MachineLearning/comments/abc/r_new_scaling_paper/", "is_self": false,
+                    "selftext_html": null, "score": 340, "num_comments": 57, "link_flair_text": "Research",
+                    "author": "alice", "subreddit": "MachineLearning", "domain": "arxiv.org",
+                    "created_utc": 1767603600.0, "stickied": false, "over_18": false
+                } },
+                { "kind": "t3", "data": {
+                    "name": "t3_def", "title": "[D] Weekly discussion", "url": "https://www.reddit.com/r/MachineLearning/comments/def/d_weekly_discussion/",
+                    "permalink": "/r/MachineLearning/comments/def/d_weekly_discussion/", "is_self": true,
+                    "selftext_html": "&lt;p&gt;Ask anything&lt;/p&gt;", "score": 12, "num_comments": 200,
+                    "link_flair_text": "Discussion", "author": "AutoModerator", "subreddit": "MachineLearning",
+                    "created_utc": 1767603600.0, "stickied": true
+                } }
+            ] }
+        }))
+        .unwrap();
+        listing.data.children.into_iter().map(|t| t.data).collect()
+    }
+
+    #[test]
+    fn test_post_to_item() {
+        let source = crate::models::Source::test("reddit", "blog", serde_json::json!({ "subreddit": "MachineLearning" }));
+        let posts = posts();
+
+        let link = post_to_item(&posts[0], DEFAULT_BASE_URL, &source);
+        assert_eq!(link.url, "https://arxiv.org/abs/2601.00001");
+        assert_eq!(link.external_id.as_deref(), Some("t3_abc"));
+        assert_eq!(link.raw_metadata["score"], 340);
+        assert_eq!(link.raw_metadata["flair"], "Research");
+        assert_eq!(
+            link.raw_metadata["discussion_url"],
+            "https://www.reddit.com/r/MachineLearning/comments/abc/r_new_scaling_paper/"
+        );
+
+        let self_post = post_to_item(&posts[1], DEFAULT_BASE_URL, &source);
+        assert_eq!(self_post.url, "https://www.reddit.com/r/MachineLearning/comments/def/d_weekly_discussion/");
+    }
+
+    #[test]
+    fn test_listing_filters() {
+        let posts = posts();
+        let options = ListingOptions::from_meta(&serde_json::json!({
+            "subreddit": "r/MachineLearning", "listing": "top", "min_score": 100, "flairs": ["research"]
+        }))
+        .unwrap();
+        assert_eq!(options.subreddit, "MachineLearning");
+        assert!(options.accepts(&posts[0]));
+        assert!(!options.accepts(&posts[1]));
+
+        let excluding = ListingOptions::from_meta(&serde_json::json!({
+            "subreddit": "MachineLearning", "exclude_flairs": ["Research"]
+        }))
+        .unwrap();
+        assert!(!excluding.accepts(&posts[0]));
+        assert!(ListingOptions::from_meta(&serde_json::json!({ "subreddit": "x", "listing": "rising" })).is_err());
+    }
+}
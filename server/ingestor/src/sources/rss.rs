@@ -1,9 +1,13 @@
-use crate::db::insert_or_update_item;
+use crate::html::{html_to_text, sanitize_html, truncate_chars};
 use crate::models::Item;
-use anyhow::Result;
+use crate::sources::archive::{is_wordpress, older_page_url, wordpress_page_url, BackfillOptions};
+use crate::sources::media::{media_metadata, parse_podcast_extras, PodcastExtras};
+use crate::urls::{canonicalize_url, is_redirect_url, resolve_canonical_url};
+use anyhow::{anyhow, Result};
 use chrono::Utc;
 use feed_rs::parser;
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 use uuid::Uuid;
 
@@ -16,31 +20,46 @@ pub async fn run_rss_ingestion(pool: &PgPool, source: &crate::models::Source) ->
         }
     };
 
+    // Respect the feed's declared ttl / sy:updatePeriod from the previous fetch
+    if let Some(next_fetch) = next_fetch_due(&source.meta) {
+        if next_fetch > Utc::now() {
+            log::info!(
+                "Skipping RSS source {}: feed asks not to refetch before {}",
+                source.name,
+                next_fetch
+            );
+            return Ok(0);
+        }
+    }
+
+    // While a WebSub lease is active the hub pushes new entries; polling resumes once it lapses
+    if crate::websub::has_active_lease(&source.meta) {
+        log::info!("Skipping RSS source {}: receiving WebSub pushes", source.name);
+        return Ok(0);
+    }
+
+    // Nothing stored from this feed yet: follow its archive links once after the first fetch.
+    // Sources that already have items (e.g. ones created before backfill existed) are left alone.
+    let first_ingestion = source.meta.get("backfill_state").is_none()
+        && !crate::db::source_has_items(pool, source.id).await?;
+
     log::info!("Starting RSS ingestion for source: {} ({})", source.name, ingest_url);
 
     // Fetch and parse the RSS/Atom feed
-    let items = fetch_rss_items(source, ingest_url).await?;
+    let content = fetch_feed(ingest_url).await?;
+    let FetchedFeed { feed_meta, items, links } = parse_feed(source, &content)?;
     log::info!("Fetched {} items from RSS feed: {}", items.len(), source.name);
 
-    // Insert or update each item in the database
-    let mut inserted = 0;
-    for item in items {
-        match insert_or_update_item(pool, &item).await {
-            Err(e) => {
-                log::warn!("Failed to insert RSS item {}: {}", item.url, e);
-            }
-            Ok(item_id) => {
-                inserted += 1;
-
-                // Extract and add topics in batch
-                let topics = crate::topics::extract_topics(&item.title, item.summary.as_deref());
-                if !topics.is_empty() {
-                    if let Err(e) = crate::db::add_item_topics_batch(pool, item_id, &topics).await {
-                        log::warn!("Failed to add topics for item {}: {}", item.url, e);
-                    }
-                }
-            }
-        }
+    if let Err(e) = crate::db::set_source_meta_key(pool, source.id, "feed", &feed_meta).await {
+        log::warn!("Failed to store feed metadata for source {}: {}", source.name, e);
+    }
+
+    let mut inserted = process_items(pool, source, items).await;
+
+    let options = BackfillOptions::from_meta(&source.meta);
+    if first_ingestion && options.max_pages > 0 {
+        let generator = feed_meta.get("generator").and_then(|g| g.as_str());
+        inserted += backfill_archive(pool, source, ingest_url, &links, generator, &options).await;
     }
 
     log::info!(
@@ -52,10 +71,138 @@ pub async fn run_rss_ingestion(pool: &PgPool, source: &crate::models::Source) ->
     Ok(inserted)
 }
 
-async fn fetch_rss_items(
+/// Store a feed document pushed by a WebSub hub, through the same path as a polled feed
+pub async fn ingest_pushed_feed(pool: &PgPool, source: &crate::models::Source, content: &[u8]) -> Result<u64> {
+    let FetchedFeed { items, .. } = parse_feed(source, content)?;
+    log::info!("Received {} pushed items for source: {}", items.len(), source.name);
+
+    Ok(process_items(pool, source, items).await)
+}
+
+/// Backfill a source on demand: ingest the current feed, then walk its archive
+pub async fn backfill_rss_source(
+    pool: &PgPool,
     source: &crate::models::Source,
-    ingest_url: &str,
-) -> Result<Vec<Item>> {
+    options: &BackfillOptions,
+) -> Result<u64> {
+    let ingest_url = source
+        .ingest_url
+        .as_deref()
+        .ok_or_else(|| anyhow!("RSS source {} has no ingest_url", source.name))?;
+
+    let content = fetch_feed(ingest_url).await?;
+    let FetchedFeed { feed_meta, items, links } = parse_feed(source, &content)?;
+    let generator = feed_meta.get("generator").and_then(|g| g.as_str());
+
+    let mut inserted = process_items(pool, source, items).await;
+    inserted += backfill_archive(pool, source, ingest_url, &links, generator, options).await;
+
+    Ok(inserted)
+}
+
+/// Follow `prev-archive` / `next` links (or WordPress `?paged=N`) from the current feed document,
+/// storing older entries until `max_pages`, the `since` date, or the end of the archive.
+/// Records the outcome in `meta.backfill_state`; returns how many items were stored.
+async fn backfill_archive(
+    pool: &PgPool,
+    source: &crate::models::Source,
+    feed_url: &str,
+    links: &[feed_rs::model::Link],
+    generator: Option<&str>,
+    options: &BackfillOptions,
+) -> u64 {
+    let wordpress = older_page_url(links, feed_url).is_none() && is_wordpress(generator);
+    let mut next_url = if wordpress {
+        wordpress_page_url(feed_url, 2)
+    } else {
+        older_page_url(links, feed_url)
+    };
+
+    let mut visited = HashSet::from([feed_url.to_string()]);
+    let mut seen_urls = HashSet::new();
+    let mut pages = 0;
+    let mut inserted = 0;
+
+    while let Some(page_url) = next_url.take() {
+        if pages >= options.max_pages || !visited.insert(page_url.clone()) {
+            break;
+        }
+
+        log::info!("Backfilling {} from {}", source.name, page_url);
+        let page = match fetch_feed(&page_url).await {
+            Ok(content) => parse_feed(source, &content),
+            Err(e) => Err(e),
+        };
+        // Past the last WordPress page the site answers with a 404 page rather than a feed
+        let FetchedFeed { items, links, .. } = match page {
+            Ok(page) => page,
+            Err(e) => {
+                log::info!("Stopping backfill of {} at {}: {}", source.name, page_url, e);
+                break;
+            }
+        };
+        pages += 1;
+
+        // A page with nothing new means the site ignored the paging parameter
+        let total = items.len();
+        let new_urls = items.iter().filter(|item| seen_urls.insert(item.url.clone())).count();
+        if new_urls == 0 {
+            break;
+        }
+
+        let items: Vec<Item> = items
+            .into_iter()
+            .filter(|item| options.since.map(|since| item.published_at >= since).unwrap_or(true))
+            .collect();
+        let reached_since = items.len() < total;
+        inserted += process_items(pool, source, items).await;
+        if reached_since {
+            break;
+        }
+
+        next_url = if wordpress {
+            wordpress_page_url(feed_url, pages + 2)
+        } else {
+            older_page_url(&links, &page_url)
+        };
+    }
+
+    log::info!("Backfilled {} items from {} archive pages for {}", inserted, pages, source.name);
+
+    let state = serde_json::json!({
+        "completed_at": Utc::now(),
+        "pages": pages,
+        "items": inserted,
+    });
+    if let Err(e) = crate::db::set_source_meta_key(pool, source.id, "backfill_state", &state).await {
+        log::warn!("Failed to store backfill state for source {}: {}", source.name, e);
+    }
+
+    inserted
+}
+
+/// Resolve URLs, optionally fetch full text, then insert/update the items
+async fn process_items(pool: &PgPool, source: &crate::models::Source, mut items: Vec<Item>) -> u64 {
+    resolve_item_urls(source, &mut items).await;
+
+    if crate::fulltext::is_enabled(source) {
+        log::info!("Fetching full article text for source: {}", source.name);
+        crate::fulltext::enrich_items(pool, &mut items).await;
+    }
+
+    // Insert or update each item in the database
+    super::store_items(pool, source, items).await
+}
+
+/// A parsed feed: feed-level metadata (stored on the source), its entries as items,
+/// and its links (for archive paging)
+struct FetchedFeed {
+    feed_meta: serde_json::Value,
+    items: Vec<Item>,
+    links: Vec<feed_rs::model::Link>,
+}
+
+async fn fetch_feed(ingest_url: &str) -> Result<Vec<u8>> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(30))
         .build()?;
@@ -64,23 +211,165 @@ async fn fetch_rss_items(
         .get(ingest_url)
         .header("User-Agent", "AI-Dashboard-Ingestor/0.1")
         .send()
-        .await?;
+        .await?
+        .error_for_status()?;
 
-    let content = response.bytes().await?;
+    Ok(response.bytes().await?.to_vec())
+}
 
+fn parse_feed(source: &crate::models::Source, content: &[u8]) -> Result<FetchedFeed> {
     // Parse the feed using feed-rs (handles both RSS and Atom)
-    let feed = parser::parse(&content[..])?;
+    let feed = parser::parse(content)?;
+    let raw_xml = String::from_utf8_lossy(content);
+    let feed_meta = feed_metadata(&feed, &raw_xml);
+    let podcast_extras = parse_podcast_extras(&raw_xml);
 
     let items = feed
         .entries
         .into_iter()
-        .filter_map(|entry| entry_to_item(entry, source))
+        .filter_map(|entry| entry_to_item(entry, source, &podcast_extras))
         .collect();
 
-    Ok(items)
+    Ok(FetchedFeed { feed_meta, items, links: feed.links })
+}
+
+/// Resolve redirects / rel=canonical for redirector links, or for every item when the source opts in
+async fn resolve_item_urls(source: &crate::models::Source, items: &mut [Item]) {
+    let resolve_all = source
+        .meta
+        .get("resolve_canonical_urls")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if !resolve_all && !items.iter().any(|item| is_redirect_url(&item.url)) {
+        return;
+    }
+
+    let client = reqwest::Client::new();
+    for item in items.iter_mut() {
+        if !resolve_all && !is_redirect_url(&item.url) {
+            continue;
+        }
+        match resolve_canonical_url(&client, &item.url).await {
+            Ok(resolved) if resolved != item.url => {
+                log::debug!("Resolved {} -> {}", item.url, resolved);
+                if let Some(obj) = item.raw_metadata.as_object_mut() {
+                    obj.entry("original_url")
+                        .or_insert_with(|| serde_json::json!(item.url));
+                }
+                item.url = resolved;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                log::warn!("Failed to resolve canonical URL for {}: {}", item.url, e);
+            }
+        }
+    }
+}
+
+/// Slack added to the feed's refresh interval so an hourly cron doesn't skip a feed with ttl=60
+const REFRESH_GRACE_MINUTES: i64 = 5;
+
+/// Longest refresh interval a feed can ask for (a yearly sy:updatePeriod would otherwise park it)
+const MAX_REFRESH_MINUTES: u32 = 60 * 24;
+
+/// Feed-level metadata persisted under `sources.meta.feed`
+fn feed_metadata(feed: &feed_rs::model::Feed, raw_xml: &str) -> serde_json::Value {
+    // Site link is the alternate link that isn't the feed itself
+    let site_url = feed
+        .links
+        .iter()
+        .find(|link| link.rel.as_deref().unwrap_or("alternate") == "alternate")
+        .map(|link| link.href.clone());
+
+    // WebSub discovery links
+    let link_with_rel = |rel: &str| {
+        feed.links
+            .iter()
+            .find(|link| link.rel.as_deref() == Some(rel))
+            .map(|link| link.href.clone())
+    };
+
+    let update_period = extract_tag_text(raw_xml, "sy:updatePeriod");
+    let update_frequency = extract_tag_text(raw_xml, "sy:updateFrequency")
+        .and_then(|f| f.parse::<u32>().ok())
+        .filter(|f| *f > 0);
+
+    // ttl wins over the syndication module when both are present
+    let refresh_minutes = feed
+        .ttl
+        .filter(|ttl| *ttl > 0)
+        .or_else(|| update_period_minutes(update_period.as_deref()?, update_frequency.unwrap_or(1)))
+        .map(|minutes| minutes.min(MAX_REFRESH_MINUTES));
+
+    serde_json::json!({
+        "title": feed.title.as_ref().map(|t| t.content.trim().to_string()),
+        "description": feed.description.as_ref().map(|d| html_to_text(&d.content)),
+        "site_url": site_url,
+        "hub_url": link_with_rel("hub"),
+        "self_url": link_with_rel("self"),
+        "icon_url": feed.icon.as_ref().map(|i| i.uri.clone()),
+        "logo_url": feed.logo.as_ref().map(|i| i.uri.clone()),
+        "language": feed.language,
+        "generator": feed.generator.as_ref().map(|g| g.content.trim().to_string()),
+        "ttl_minutes": feed.ttl,
+        "update_period": update_period,
+        "update_frequency": update_frequency,
+        "refresh_minutes": refresh_minutes,
+        "fetched_at": Utc::now(),
+    })
 }
 
-fn entry_to_item(entry: feed_rs::model::Entry, source: &crate::models::Source) -> Option<Item> {
+/// Minutes between updates for `sy:updatePeriod` / `sy:updateFrequency`
+fn update_period_minutes(period: &str, frequency: u32) -> Option<u32> {
+    let period_minutes = match period.trim().to_lowercase().as_str() {
+        "hourly" => 60,
+        "daily" => 60 * 24,
+        "weekly" => 60 * 24 * 7,
+        "monthly" => 60 * 24 * 30,
+        "yearly" => 60 * 24 * 365,
+        _ => return None,
+    };
+    Some(period_minutes / frequency.max(1))
+}
+
+/// Text of the first `<tag>...</tag>` in the raw document (for namespaced elements feed-rs ignores)
+fn extract_tag_text(raw_xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = raw_xml.find(&open)? + open.len();
+    let end = raw_xml[start..].find(&close)? + start;
+    let text = raw_xml[start..end].trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// When the source may be fetched again, based on the metadata stored by the last fetch
+fn next_fetch_due(meta: &serde_json::Value) -> Option<chrono::DateTime<Utc>> {
+    let feed = meta.get("feed")?;
+    let fetched_at = feed
+        .get("fetched_at")?
+        .as_str()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())?
+        .with_timezone(&Utc);
+    // Metadata stored before the cap existed may hold a longer interval
+    let refresh_minutes = feed.get("refresh_minutes")?.as_i64()?.min(MAX_REFRESH_MINUTES as i64);
+
+    Some(fetched_at + chrono::Duration::minutes(refresh_minutes - REFRESH_GRACE_MINUTES))
+}
+
+pub(super) fn entry_to_item(
+    entry: feed_rs::model::Entry,
+    source: &crate::models::Source,
+    podcast_extras: &HashMap<String, PodcastExtras>,
+) -> Option<Item> {
+    // Enclosures, duration, episode number and transcripts (podcasts, video)
+    let extras = podcast_extras.get(&entry.id).or_else(|| {
+        entry
+            .links
+            .iter()
+            .find_map(|link| podcast_extras.get(&link.href))
+    });
+    let media = media_metadata(&entry, extras);
+
     // Extract title
     let title = entry.title.map(|t| t.content).unwrap_or_else(|| {
         format!(
@@ -97,50 +386,36 @@ fn entry_to_item(entry: feed_rs::model::Entry, source: &crate::models::Source) -
         .and_then(|link| Some(link.href.clone()))
         .or_else(|| Some(entry.id.clone()));
 
-    // If no URL found, skip this entry
-    let url = url?;
+    // If no URL found, skip this entry; keep the feed's link around for raw_metadata
+    let original_url = url?;
+    let url = canonicalize_url(&original_url);
+
+    // Feed guid / Atom id (feed-rs derives a stable one when the feed omits it)
+    let external_id = Some(entry.id.trim().to_string()).filter(|id| !id.is_empty());
 
-    // Extract summary
+    // Extract summary as plain text (falls back to the start of the content)
     let summary = entry
         .summary
-        .map(|s| s.content)
+        .map(|s| html_to_text(&s.content))
+        .filter(|s| !s.is_empty())
         .or_else(|| {
-            // Try to get first content entry
             entry
                 .content
                 .as_ref()
-                .and_then(|content| content.body.clone())
-                .map(|body| {
-                    // Limit summary to first 500 chars
-                    let truncated = body
-                        .chars()
-                        .take(500)
-                        .collect::<String>();
-                    if truncated.len() < body.len() {
-                        format!("{}...", truncated)
-                    } else {
-                        body
-                    }
-                })
-        });
+                .and_then(|content| content.body.as_deref())
+                .map(html_to_text)
+        })
+        .filter(|s| !s.is_empty())
+        .map(|s| truncate_chars(&s, 500));
 
-    // Extract body (full content if available)
-    let body = entry.content.as_ref().and_then(|content| {
-        content
-            .body
-            .as_ref()
-            .map(|b| {
-                let truncated = b
-                    .chars()
-                    .take(10000)
-                    .collect::<String>();
-                if truncated.len() < b.len() {
-                    format!("{}...", truncated)
-                } else {
-                    b.clone()
-                }
-            })
-    });
+    // Extract body (full content if available) as sanitized HTML.
+    // Truncate before sanitizing so the parser closes any tag we cut through.
+    let body = entry
+        .content
+        .as_ref()
+        .and_then(|content| content.body.as_deref())
+        .map(|b| sanitize_html(&truncate_chars(b, 10000)))
+        .filter(|b| !b.trim().is_empty());
 
     // Extract published date
     let published_at = entry
@@ -161,6 +436,18 @@ fn entry_to_item(entry: feed_rs::model::Entry, source: &crate::models::Source) -
             .collect::<Vec<_>>(),
     });
 
+    if let Some(media) = media {
+        if let Some(obj) = metadata.as_object_mut() {
+            obj.insert("media".to_string(), media);
+        }
+    }
+
+    if original_url != url {
+        if let Some(obj) = metadata.as_object_mut() {
+            obj.insert("original_url".to_string(), serde_json::json!(original_url));
+        }
+    }
+
     // Add feed links to metadata
     if !entry.links.is_empty() {
         if let Some(obj) = metadata.as_object_mut() {
@@ -184,6 +471,7 @@ fn entry_to_item(entry: feed_rs::model::Entry, source: &crate::models::Source) -
         id: Uuid::new_v4(),
         source_id: source.id,
         source_type: source.medium.clone(),
+        external_id,
         title,
         url,
         summary,
@@ -194,3 +482,56 @@ fn entry_to_item(entry: feed_rs::model::Entry, source: &crate::models::Source) -
         updated_at: Utc::now(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED: &str = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+<channel>
+  <title>Example Blog</title>
+  <link>https://example.com/</link>
+  <description>Notes on &lt;b&gt;AI&lt;/b&gt;</description>
+  <language>en-us</language>
+  <generator>WordPress</generator>
+  <image><url>https://example.com/logo.png</url><title>Example</title><link>https://example.com/</link></image>
+  <sy:updatePeriod>daily</sy:updatePeriod>
+  <sy:updateFrequency>2</sy:updateFrequency>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn test_feed_metadata() {
+        let feed = parser::parse(FEED.as_bytes()).unwrap();
+        let meta = feed_metadata(&feed, FEED);
+
+        assert_eq!(meta["title"], "Example Blog");
+        assert_eq!(meta["description"], "Notes on AI");
+        assert_eq!(meta["site_url"], "https://example.com/");
+        assert_eq!(meta["logo_url"], "https://example.com/logo.png");
+        assert_eq!(meta["language"], "en-us");
+        assert_eq!(meta["generator"], "WordPress");
+        assert_eq!(meta["refresh_minutes"], 720);
+    }
+
+    #[test]
+    fn test_next_fetch_due() {
+        let meta = serde_json::json!({
+            "feed": { "fetched_at": "2026-01-01T10:00:00Z", "refresh_minutes": 60 }
+        });
+        assert_eq!(
+            next_fetch_due(&meta).unwrap().to_rfc3339(),
+            "2026-01-01T10:55:00+00:00"
+        );
+        assert!(next_fetch_due(&serde_json::json!({})).is_none());
+
+        let yearly = serde_json::json!({
+            "feed": { "fetched_at": "2026-01-01T10:00:00Z", "refresh_minutes": 525600 }
+        });
+        assert_eq!(
+            next_fetch_due(&yearly).unwrap().to_rfc3339(),
+            "2026-01-02T09:55:00+00:00"
+        );
+    }
+}
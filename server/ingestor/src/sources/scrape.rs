@@ -0,0 +1,303 @@
+//! Listing-page scraping for `scrape` sources, for sites without a feed
+//!
+//! `meta` describes the page:
+//!   `{"listing_url": "https://example.com/research",
+//!     "selectors": {"item": "article.post", "title": "h2", "link": "a", "date": "time", "summary": "p.excerpt"},
+//!     "date_format": "%B %d, %Y"}`
+//! Only `selectors.item` is required: the title defaults to the link text and the link to the
+//! first `a[href]` in the item. `listing_url` falls back to `ingest_url`. Dates come from a
+//! `datetime`/`content` attribute when present, else the element text (with `date_format`
+//! or common formats). A hash of the extracted items is kept in `meta.scrape_state`, so an
+//! unchanged listing is not written again.
+
+use crate::html::truncate_chars;
+use crate::models::Item;
+use crate::pages::parse_date;
+use crate::urls::{canonicalize_url, url_key};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use scraper::{ElementRef, Html, Selector};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use url::Url;
+use uuid::Uuid;
+
+
+/// Where the listing is and how to read it
+#[derive(Debug, Clone)]
+pub struct ScrapeConfig {
+    pub listing_url: String,
+    pub item: Selector,
+    pub title: Option<Selector>,
+    pub link: Option<Selector>,
+    pub date: Option<Selector>,
+    pub summary: Option<Selector>,
+    pub date_format: Option<String>,
+}
+
+impl ScrapeConfig {
+    pub fn from_source(source: &crate::models::Source) -> Result<Self> {
+        let meta = &source.meta;
+        let listing_url = meta
+            .get("listing_url")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or_else(|| source.ingest_url.clone())
+            .filter(|url| !url.trim().is_empty())
+            .ok_or_else(|| anyhow!("meta.listing_url or ingest_url is required"))?;
+
+        let selectors = meta.get("selectors").cloned().unwrap_or_default();
+        let selector = |key: &str| -> Result<Option<Selector>> {
+            match selectors.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()) {
+                Some(css) => Selector::parse(css)
+                    .map(Some)
+                    .map_err(|e| anyhow!("Invalid selectors.{} '{}': {}", key, css, e)),
+                None => Ok(None),
+            }
+        };
+
+        Ok(Self {
+            listing_url,
+            item: selector("item")?.ok_or_else(|| anyhow!("meta.selectors.item is required"))?,
+            title: selector("title")?,
+            link: selector("link")?,
+            date: selector("date")?,
+            summary: selector("summary")?,
+            date_format: meta
+                .get("date_format")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .filter(|f| !f.is_empty()),
+        })
+    }
+}
+
+/// One entry found on the listing page
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrapedEntry {
+    pub title: String,
+    pub url: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub summary: Option<String>,
+}
+
+pub async fn run_scrape_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let config = ScrapeConfig::from_source(source)
+        .map_err(|e| anyhow!("Invalid scrape source {}: {}", source.name, e))?;
+
+    log::info!("Starting scrape ingestion for source: {} ({})", source.name, config.listing_url);
+
+    let page = crate::http::shared().get_text(&config.listing_url).await?;
+    let entries = extract_entries(&page, &config)?;
+    if entries.is_empty() {
+        log::warn!("No entries matched the selectors for source: {}", source.name);
+        return Ok(0);
+    }
+
+    // Skip writes when the listing looks exactly as it did last time
+    let hash = entries_hash(&entries);
+    let previous_hash = source
+        .meta
+        .get("scrape_state")
+        .and_then(|s| s.get("hash"))
+        .and_then(|h| h.as_str());
+    if previous_hash == Some(hash.as_str()) {
+        log::info!("Listing unchanged for source: {}", source.name);
+        return Ok(0);
+    }
+
+    log::info!("Scraped {} entries for source: {}", entries.len(), source.name);
+
+    // Undated entries would get a fresh published_at on every write; store those only once
+    let undated: Vec<String> = entries
+        .iter()
+        .filter(|entry| entry.published_at.is_none())
+        .map(|entry| entry.url.clone())
+        .collect();
+    let stored = crate::db::find_existing_url_keys(pool, source.id, &undated).await?;
+    let items: Vec<Item> = entries
+        .iter()
+        .filter(|entry| entry.published_at.is_some() || !stored.contains(&url_key(&entry.url)))
+        .map(|entry| entry_to_item(entry, source))
+        .collect();
+    let attempted = items.len() as u64;
+    let inserted = super::store_items(pool, source, items).await;
+
+    // A failed write must not be skipped as "unchanged" next cycle, so the hash waits for a clean run
+    if inserted < attempted {
+        log::warn!(
+            "Stored {} of {} entries for source {}; the listing will be processed again",
+            inserted,
+            attempted,
+            source.name
+        );
+    } else {
+        let state = serde_json::json!({
+            "hash": hash,
+            "entry_count": entries.len(),
+            "changed_at": Utc::now().to_rfc3339(),
+        });
+        if let Err(e) = crate::db::set_source_meta_key(pool, source.id, "scrape_state", &state).await {
+            log::warn!("Failed to store scrape state for source {}: {}", source.name, e);
+        }
+    }
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+/// Apply the configured selectors to a listing page
+pub fn extract_entries(page: &str, config: &ScrapeConfig) -> Result<Vec<ScrapedEntry>> {
+    let base = Url::parse(&config.listing_url)?;
+    let document = Html::parse_document(page);
+    let any_link = Selector::parse("a[href]").map_err(|e| anyhow!("{}", e))?;
+
+    let mut entries: Vec<ScrapedEntry> = Vec::new();
+    for container in document.select(&config.item) {
+        let link_element = match &config.link {
+            Some(link) => container.select(link).next(),
+            None => container.select(&any_link).next(),
+        };
+        // A selector may point at the anchor itself or at something wrapping it
+        let href = link_element.and_then(|el| {
+            el.value()
+                .attr("href")
+                .or_else(|| el.select(&any_link).next().and_then(|a| a.value().attr("href")))
+        });
+        let href = href.or_else(|| container.value().attr("href"));
+        let Some(url) = href.and_then(|href| base.join(href.trim()).ok()) else {
+            continue;
+        };
+
+        let title = config
+            .title
+            .as_ref()
+            .and_then(|title| container.select(title).next())
+            .or(link_element)
+            .map(element_text)
+            .filter(|t| !t.is_empty());
+        let Some(title) = title else { continue };
+
+        let published_at = config
+            .date
+            .as_ref()
+            .and_then(|date| container.select(date).next())
+            .and_then(|el| {
+                el.value()
+                    .attr("datetime")
+                    .or_else(|| el.value().attr("content"))
+                    .map(str::to_string)
+                    .or_else(|| Some(element_text(el)))
+            })
+            .and_then(|text| parse_date(&text, config.date_format.as_deref()));
+
+        let summary = config
+            .summary
+            .as_ref()
+            .and_then(|summary| container.select(summary).next())
+            .map(element_text)
+            .filter(|s| !s.is_empty())
+            .map(|s| truncate_chars(&s, 500));
+
+        let url = canonicalize_url(url.as_str());
+        if entries.iter().any(|e| e.url == url) {
+            continue;
+        }
+        entries.push(ScrapedEntry { title, url, published_at, summary });
+    }
+
+    Ok(entries)
+}
+
+fn element_text(element: ElementRef) -> String {
+    element.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+
+fn entries_hash(entries: &[ScrapedEntry]) -> String {
+    let mut hasher = Sha256::new();
+    for entry in entries {
+        hasher.update(entry.url.as_bytes());
+        hasher.update([0]);
+        hasher.update(entry.title.as_bytes());
+        hasher.update([0]);
+        hasher.update(entry.summary.as_deref().unwrap_or_default().as_bytes());
+        hasher.update([0]);
+        hasher.update(entry.published_at.map(|d| d.to_rfc3339()).unwrap_or_default().as_bytes());
+        hasher.update([0xff]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn entry_to_item(entry: &ScrapedEntry, source: &crate::models::Source) -> Item {
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: None,
+        title: entry.title.clone(),
+        url: entry.url.clone(),
+        summary: entry.summary.clone(),
+        body: None,
+        published_at: entry.published_at.unwrap_or_else(Utc::now),
+        raw_metadata: serde_json::json!({ "scraped": true, "date_found": entry.published_at.is_some() }),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const LISTING: &str = r#"<html><body>
+      <nav><a href="/about">About</a></nav>
+      <div class="posts">
+        <article class="post">
+          <h2><a href="/research/scaling">Scaling   laws for agents</a></h2>
+          <time datetime="2026-01-05T09:00:00Z">Jan 5</time>
+          <p class="excerpt">We study how agents scale.</p>
+        </article>
+        <article class="post">
+          <a class="card" href="https://example.com/research/eval?utm_source=site"><span>New eval suite</span></a>
+          <span class="date">January 2, 2026</span>
+        </article>
+        <article class="post"><p>No link here</p></article>
+      </div>
+    </body></html>"#;
+
+    fn source(meta: serde_json::Value) -> crate::models::Source {
+        crate::models::Source {
+            ingest_url: Some("https://example.com/research".to_string()),
+            ..crate::models::Source::test("scrape", "blog", meta)
+        }
+    }
+
+    #[test]
+    fn test_extract_entries() {
+        let config = ScrapeConfig::from_source(&source(serde_json::json!({
+            "selectors": { "item": "article.post", "title": "h2", "date": "time, .date", "summary": "p.excerpt" }
+        })))
+        .unwrap();
+
+        let entries = extract_entries(LISTING, &config).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].title, "Scaling laws for agents");
+        assert_eq!(entries[0].url, "https://example.com/research/scaling");
+        assert_eq!(entries[0].published_at, Some(Utc.with_ymd_and_hms(2026, 1, 5, 9, 0, 0).unwrap()));
+        assert_eq!(entries[0].summary.as_deref(), Some("We study how agents scale."));
+
+        assert_eq!(entries[1].title, "New eval suite");
+        assert_eq!(entries[1].url, "https://example.com/research/eval");
+        assert_eq!(entries[1].published_at, Some(Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap()));
+
+        assert_eq!(entries_hash(&entries), entries_hash(&extract_entries(LISTING, &config).unwrap()));
+    }
+
+    #[test]
+    fn test_config_requires_item_selector() {
+        assert!(ScrapeConfig::from_source(&source(serde_json::json!({ "selectors": { "title": "h2" } }))).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Sitemap ingestion for `sitemap` sources, for sites with a sitemap but no feed
+//!
+//! Options in `meta`:
+//!   `sitemap_url` (falls back to `ingest_url`; a bare site URL means `/sitemap.xml`),
+//!   `path_pattern` (regex matched against the URL path, e.g. `^/blog/`),
+//!   `since_days` (skip URLs and child sitemaps whose `lastmod` is older),
+//!   `max_new` (pages fetched per cycle, default 20), `max_sitemaps` (default 20)
+//! Sitemap indexes are followed. Only URLs not stored yet are fetched, newest `lastmod` first;
+//! title, description, publish date and OpenGraph data come from the page itself.
+
+use crate::html::truncate_chars;
+use crate::models::Item;
+use crate::pages::extract_page_metadata;
+use crate::pages::parse_date;
+use crate::urls::{canonicalize_url, url_key};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use regex::Regex;
+use sqlx::PgPool;
+use url::Url;
+use uuid::Uuid;
+
+const DEFAULT_MAX_NEW: usize = 20;
+const DEFAULT_MAX_SITEMAPS: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct SitemapOptions {
+    pub sitemap_url: String,
+    pub path_pattern: Option<Regex>,
+    pub since: Option<DateTime<Utc>>,
+    pub max_new: usize,
+    pub max_sitemaps: usize,
+}
+
+impl SitemapOptions {
+    pub fn from_source(source: &crate::models::Source) -> Result<Self> {
+        let meta = &source.meta;
+        let url = meta
+            .get("sitemap_url")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or_else(|| source.ingest_url.clone())
+            .filter(|url| !url.trim().is_empty())
+            .ok_or_else(|| anyhow!("meta.sitemap_url or ingest_url is required"))?;
+        let mut sitemap_url = Url::parse(url.trim())?;
+        if sitemap_url.path() == "/" || sitemap_url.path().is_empty() {
+            sitemap_url.set_path("/sitemap.xml");
+        }
+
+        let path_pattern = match meta.get("path_pattern").and_then(|v| v.as_str()).filter(|p| !p.is_empty()) {
+            Some(pattern) => Some(Regex::new(pattern).map_err(|e| anyhow!("Invalid meta.path_pattern: {}", e))?),
+            None => None,
+        };
+        let count = |key: &str, default: usize| {
+            meta.get(key).and_then(|v| v.as_u64()).map(|n| n as usize).unwrap_or(default)
+        };
+
+        Ok(Self {
+            sitemap_url: sitemap_url.to_string(),
+            path_pattern,
+            since: meta
+                .get("since_days")
+                .and_then(|v| v.as_i64())
+                .map(|days| Utc::now() - chrono::Duration::days(days)),
+            max_new: count("max_new", DEFAULT_MAX_NEW),
+            max_sitemaps: count("max_sitemaps", DEFAULT_MAX_SITEMAPS),
+        })
+    }
+
+    fn accepts_sitemap(&self, entry: &SitemapEntry) -> bool {
+        match (self.since, entry.lastmod) {
+            (Some(since), Some(lastmod)) => lastmod >= since,
+            _ => true,
+        }
+    }
+
+    fn accepts(&self, entry: &SitemapEntry) -> bool {
+        let recent = self.accepts_sitemap(entry);
+        let path_matches = match &self.path_pattern {
+            Some(pattern) => Url::parse(&entry.loc).map(|url| pattern.is_match(url.path())).unwrap_or(false),
+            None => true,
+        };
+        recent && path_matches
+    }
+}
+
+/// A `<url>` or `<sitemap>` entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+/// A parsed sitemap document: page URLs, or child sitemaps for an index
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sitemap {
+    pub urls: Vec<SitemapEntry>,
+    pub sitemaps: Vec<SitemapEntry>,
+}
+
+pub async fn run_sitemap_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let options = SitemapOptions::from_source(source)
+        .map_err(|e| anyhow!("Invalid sitemap source {}: {}", source.name, e))?;
+
+    log::info!("Starting sitemap ingestion for source: {} ({})", source.name, options.sitemap_url);
+
+    let fetcher = crate::http::shared();
+    let entries = collect_entries(&options).await?;
+    log::info!("Found {} matching URLs in sitemap for source: {}", entries.len(), source.name);
+
+    // One query for the whole sitemap instead of one per URL
+    let urls: Vec<String> = entries.iter().map(|entry| canonicalize_url(&entry.loc)).collect();
+    let stored = crate::db::find_existing_url_keys(pool, source.id, &urls).await?;
+
+    let mut items = Vec::new();
+    for (entry, url) in entries.iter().zip(&urls) {
+        if items.len() >= options.max_new {
+            break;
+        }
+        if stored.contains(&url_key(url)) {
+            continue;
+        }
+
+        match fetcher.get_text(&entry.loc).await {
+            Ok(page) => items.push(page_to_item(entry, &page, source)),
+            Err(e) => log::warn!("Failed to fetch {}: {}", entry.loc, e),
+        }
+    }
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+/// Walk the sitemap (and any child sitemaps), returning matching URLs newest first
+async fn collect_entries(options: &SitemapOptions) -> Result<Vec<SitemapEntry>> {
+    let fetcher = crate::http::shared();
+    let mut pending = vec![options.sitemap_url.clone()];
+    let mut fetched = 0;
+    let mut entries: Vec<SitemapEntry> = Vec::new();
+
+    while let Some(sitemap_url) = pending.pop() {
+        if fetched >= options.max_sitemaps {
+            log::warn!("Stopped after {} sitemaps; raise meta.max_sitemaps to read more", fetched);
+            break;
+        }
+        if sitemap_url.ends_with(".gz") {
+            log::warn!("Skipping compressed sitemap {}", sitemap_url);
+            continue;
+        }
+
+        let xml = match fetcher.get_text(&sitemap_url).await {
+            Ok(xml) => xml,
+            // The root sitemap has to work; a broken child only loses its own URLs
+            Err(e) if fetched == 0 => return Err(e),
+            Err(e) => {
+                log::warn!("Failed to fetch sitemap {}: {}", sitemap_url, e);
+                continue;
+            }
+        };
+        fetched += 1;
+
+        let sitemap = parse_sitemap(&xml);
+        pending.extend(
+            sitemap
+                .sitemaps
+                .into_iter()
+                .filter(|child| options.accepts_sitemap(child))
+                .map(|child| child.loc),
+        );
+        entries.extend(sitemap.urls.into_iter().filter(|entry| options.accepts(entry)));
+    }
+
+    // Undated URLs go last; among dated ones the newest first
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.lastmod));
+    let mut seen = std::collections::HashSet::new();
+    entries.retain(|entry| seen.insert(entry.loc.clone()));
+    Ok(entries)
+}
+
+pub fn parse_sitemap(xml: &str) -> Sitemap {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut sitemap = Sitemap::default();
+    let mut current_tag = String::new();
+    let mut loc: Option<String> = None;
+    let mut lastmod: Option<DateTime<Utc>> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = tag_name(e.name().as_ref());
+                if name == "url" || name == "sitemap" {
+                    loc = None;
+                    lastmod = None;
+                }
+                current_tag = name;
+            }
+            Ok(Event::Text(text)) => {
+                let Ok(text) = text.unescape() else { continue };
+                match current_tag.as_str() {
+                    "loc" => loc = Some(text.trim().to_string()),
+                    "lastmod" => lastmod = parse_date(&text, None),
+                    _ => {}
+                }
+            }
+            Ok(Event::CData(text)) if current_tag == "loc" => {
+                loc = Some(String::from_utf8_lossy(&text).trim().to_string());
+            }
+            Ok(Event::End(e)) => {
+                let name = tag_name(e.name().as_ref());
+                if name == "url" || name == "sitemap" {
+                    if let Some(loc) = loc.take() {
+                        let entry = SitemapEntry { loc, lastmod: lastmod.take() };
+                        if name == "url" {
+                            sitemap.urls.push(entry);
+                        } else {
+                            sitemap.sitemaps.push(entry);
+                        }
+                    }
+                }
+                current_tag.clear();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                log::debug!("Stopped reading sitemap: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    sitemap
+}
+
+/// Tag names keep their prefix, so extension tags like `image:loc` never match `loc`
+fn tag_name(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).to_string()
+}
+
+fn page_to_item(entry: &SitemapEntry, page: &str, source: &crate::models::Source) -> Item {
+    let base = Url::parse(&entry.loc).ok();
+    let metadata = base.as_ref().map(|base| extract_page_metadata(page, base)).unwrap_or_default();
+
+    let title = metadata.title.clone().unwrap_or_else(|| entry.loc.clone());
+    let mut raw_metadata = metadata.to_json();
+    if let Some(obj) = raw_metadata.as_object_mut() {
+        obj.insert("lastmod".to_string(), serde_json::json!(entry.lastmod.map(|d| d.to_rfc3339())));
+    }
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: source.medium.clone(),
+        external_id: None,
+        title,
+        url: canonicalize_url(&entry.loc),
+        summary: metadata.description.as_deref().map(|d| truncate_chars(d, 500)),
+        body: None,
+        published_at: metadata.published_at.or(entry.lastmod).unwrap_or_else(Utc::now),
+        raw_metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_sitemap_and_index() {
+        let urlset = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+  <url><loc>https://example.com/blog/scaling</loc><lastmod>2026-01-05</lastmod>
+    <image:image><image:loc>https://example.com/cover.png</image:loc></image:image></url>
+  <url><loc>https://example.com/about</loc></url>
+</urlset>"#;
+        let sitemap = parse_sitemap(urlset);
+        assert_eq!(sitemap.urls.len(), 2);
+        assert_eq!(sitemap.urls[0].loc, "https://example.com/blog/scaling");
+        assert_eq!(sitemap.urls[0].lastmod, Some(Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap()));
+        assert!(sitemap.sitemaps.is_empty());
+
+        let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/post-sitemap.xml</loc><lastmod>2026-01-05T10:00:00+00:00</lastmod></sitemap>
+</sitemapindex>"#;
+        let sitemap = parse_sitemap(index);
+        assert_eq!(sitemap.sitemaps[0].loc, "https://example.com/post-sitemap.xml");
+        assert!(sitemap.urls.is_empty());
+    }
+
+    #[test]
+    fn test_options_filter_by_path() {
+        let source = crate::models::Source {
+            ingest_url: Some("https://example.com".to_string()),
+            ..crate::models::Source::test("sitemap", "blog", serde_json::json!({ "path_pattern": "^/blog/" }))
+        };
+        let options = SitemapOptions::from_source(&source).unwrap();
+        assert_eq!(options.sitemap_url, "https://example.com/sitemap.xml");
+
+        let entry = |loc: &str| SitemapEntry { loc: loc.to_string(), lastmod: None };
+        assert!(options.accepts(&entry("https://example.com/blog/scaling")));
+        assert!(!options.accepts(&entry("https://example.com/about")));
+
+        let page = r#"<html><head><meta property="og:title" content="Scaling"><meta name="description" content="Notes"></head></html>"#;
+        let item = page_to_item(&entry("https://example.com/blog/scaling"), page, &source);
+        assert_eq!(item.title, "Scaling");
+        assert_eq!(item.summary.as_deref(), Some("Notes"));
+    }
+}
@@ -0,0 +1,503 @@
+//! Twitter/X API v2 ingestion for `twitter_api` sources
+//!
+//! `meta.mode` selects the timeline:
+//!   `{"mode": "list", "list_id": "..."}`, `{"mode": "user_timeline", "handle": "..."}`
+//!   or `{"mode": "search", "query": "..."}`
+//! Requests use `TWITTER_BEARER_TOKEN` (read into `Config`). The API root defaults to `https://api.twitter.com/2`;
+//! `TWITTER_API_BASE_URL` (or the source's `ingest_url`) points it at a mock for local testing.
+
+use crate::config::Config;
+use crate::models::{Item, Medium};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+const DEFAULT_API_BASE_URL: &str = "https://api.twitter.com/2";
+
+/// Pages of up to 100 tweets fetched per cycle unless `meta.max_pages` says otherwise
+const DEFAULT_MAX_PAGES: u32 = 3;
+
+const TWEET_FIELDS: &str = "created_at,author_id,public_metrics,lang,conversation_id,referenced_tweets";
+const USER_FIELDS: &str = "username,name";
+
+/// The timeline a source follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Timeline {
+    List { list_id: String },
+    UserTweets { handle: String },
+    Search { query: String },
+}
+
+impl Timeline {
+    pub fn from_meta(meta: &serde_json::Value) -> Result<Self> {
+        let field = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| anyhow!("meta.{} is required", key))
+        };
+
+        match meta.get("mode").and_then(|v| v.as_str()) {
+            Some("list") => Ok(Timeline::List { list_id: field("list_id")? }),
+            Some("user_timeline") => Ok(Timeline::UserTweets {
+                handle: field("handle")?.trim_start_matches('@').to_string(),
+            }),
+            Some("search") => Ok(Timeline::Search { query: field("query")? }),
+            Some(mode) => Err(anyhow!("Unknown twitter_api mode: {}", mode)),
+            None => Err(anyhow!("meta.mode is required")),
+        }
+    }
+}
+
+/// Sync position stored as `sources.meta.twitter_state`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since_id: Option<String>,
+    /// Where a run cut short by `max_pages` stopped paging; the next run resumes there, and
+    /// `since_id` only moves on (to `pending_since_id`) once the backlog is drained
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next_token: Option<String>,
+    /// Newest tweet id fetched while a backlog is being paged through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending_since_id: Option<String>,
+    /// Resolved id of `meta.handle` (looked up once)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    handle: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TweetsResponse {
+    #[serde(default)]
+    data: Vec<Tweet>,
+    #[serde(default)]
+    includes: Includes,
+    #[serde(default)]
+    meta: ResponseMeta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Includes {
+    #[serde(default)]
+    users: Vec<User>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ResponseMeta {
+    next_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tweet {
+    pub id: String,
+    pub text: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub author_id: Option<String>,
+    pub public_metrics: Option<serde_json::Value>,
+    pub lang: Option<String>,
+    pub conversation_id: Option<String>,
+    #[serde(default)]
+    pub referenced_tweets: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    data: User,
+}
+
+pub struct TwitterClient {
+    client: reqwest::Client,
+    base_url: String,
+    bearer_token: String,
+}
+
+impl TwitterClient {
+    pub fn new(base_url: &str, bearer_token: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(crate::http::USER_AGENT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            bearer_token: bearer_token.to_string(),
+        })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str, params: &[(&str, &str)]) -> Result<T> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.bearer_token)
+            .query(params)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Twitter API {} returned {}: {}", path, status, body.chars().take(200).collect::<String>()));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn user_by_handle(&self, handle: &str) -> Result<User> {
+        let response: UserResponse = self
+            .get(&format!("/users/by/username/{}", urlencoding::encode(handle)), &[])
+            .await?;
+        Ok(response.data)
+    }
+
+    /// Tweets newer than `since_id` (newest first), following `next_token` from `start_token`
+    /// for up to `max_pages`. The list endpoint has no `since_id`, so its pages are cut off at the
+    /// first already-seen tweet. Also returns the token to resume from when `max_pages` ran out
+    /// before the last page.
+    pub async fn fetch_tweets(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        since_id: Option<&str>,
+        supports_since_id: bool,
+        start_token: Option<&str>,
+        max_pages: u32,
+    ) -> Result<(Vec<Tweet>, HashMap<String, User>, Option<String>)> {
+        // Search pages with `next_token`, the timeline endpoints with `pagination_token`
+        let token_param = if query.is_some() { "next_token" } else { "pagination_token" };
+
+        let mut tweets = Vec::new();
+        let mut users = HashMap::new();
+        let mut next_token: Option<String> = start_token.map(str::to_string);
+
+        for _ in 0..max_pages {
+            let mut params = vec![
+                ("max_results", "100"),
+                ("tweet.fields", TWEET_FIELDS),
+                ("expansions", "author_id"),
+                ("user.fields", USER_FIELDS),
+            ];
+            if let Some(query) = query {
+                params.push(("query", query));
+            }
+            if supports_since_id {
+                if let Some(since_id) = since_id {
+                    params.push(("since_id", since_id));
+                }
+            }
+            if let Some(token) = next_token.as_deref() {
+                params.push((token_param, token));
+            }
+
+            let page: TweetsResponse = self.get(path, &params).await?;
+            for user in page.includes.users {
+                users.insert(user.id.clone(), user);
+            }
+
+            let page_size = page.data.len();
+            let before = tweets.len();
+            tweets.extend(
+                page.data
+                    .into_iter()
+                    .filter(|tweet| since_id.map(|since| is_newer(&tweet.id, since)).unwrap_or(true)),
+            );
+            // Pages are newest first: once one contains a seen tweet, the rest are older still
+            let reached_seen = tweets.len() - before < page_size;
+
+            next_token = page.meta.next_token;
+            if reached_seen {
+                next_token = None;
+            }
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok((tweets, users, next_token))
+    }
+}
+
+/// Tweet ids are snowflakes: numerically ordered, but transported as strings
+fn is_newer(id: &str, than: &str) -> bool {
+    match (id.parse::<u64>(), than.parse::<u64>()) {
+        (Ok(id), Ok(than)) => id > than,
+        _ => id.len() > than.len() || (id.len() == than.len() && id > than),
+    }
+}
+
+pub async fn run_twitter_ingestion(pool: &PgPool, source: &crate::models::Source, config: &Config) -> Result<u64> {
+    let timeline = Timeline::from_meta(&source.meta)
+        .map_err(|e| anyhow!("Invalid twitter_api source {}: {}", source.name, e))?;
+    let bearer_token = config
+        .twitter_bearer_token
+        .as_deref()
+        .ok_or_else(|| anyhow!("TWITTER_BEARER_TOKEN environment variable not set"))?;
+    let base_url = source
+        .ingest_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())
+        .or_else(|| config.twitter_api_base_url.clone())
+        .unwrap_or_else(|| DEFAULT_API_BASE_URL.to_string());
+    let max_pages = source
+        .meta
+        .get("max_pages")
+        .and_then(|v| v.as_u64())
+        .map(|pages| pages.clamp(1, 50) as u32)
+        .unwrap_or(DEFAULT_MAX_PAGES);
+
+    log::info!("Starting Twitter ingestion for source: {} ({:?})", source.name, timeline);
+
+    let client = TwitterClient::new(&base_url, bearer_token)?;
+    let mut state: SyncState = source
+        .meta
+        .get("twitter_state")
+        .and_then(|state| serde_json::from_value(state.clone()).ok())
+        .unwrap_or_default();
+
+    let (path, query, supports_since_id) = match &timeline {
+        Timeline::List { list_id } => (format!("/lists/{}/tweets", urlencoding::encode(list_id)), None, false),
+        Timeline::UserTweets { handle } => {
+            let user_id = match (&state.user_id, &state.handle) {
+                (Some(user_id), Some(known)) if known.eq_ignore_ascii_case(handle) => user_id.clone(),
+                _ => {
+                    let user = client.user_by_handle(handle).await?;
+                    state.handle = Some(handle.clone());
+                    state.user_id = Some(user.id.clone());
+                    user.id
+                }
+            };
+            (format!("/users/{}/tweets", user_id), None, true)
+        }
+        Timeline::Search { query } => ("/tweets/search/recent".to_string(), Some(query.as_str()), true),
+    };
+
+    let since_id = state.since_id.clone();
+    let fetched = client
+        .fetch_tweets(&path, query, since_id.as_deref(), supports_since_id, state.next_token.as_deref(), max_pages)
+        .await;
+    let (tweets, users, next_token) = match fetched {
+        Ok(fetched) => fetched,
+        // A pagination token can expire between cycles: give up on that backlog and start from the top
+        Err(e) if state.next_token.is_some() => {
+            log::warn!("Resuming paging failed for source {}, starting over: {}", source.name, e);
+            state.next_token = None;
+            client
+                .fetch_tweets(&path, query, since_id.as_deref(), supports_since_id, None, max_pages)
+                .await?
+        }
+        Err(e) => return Err(e),
+    };
+
+    log::info!("Fetched {} tweets for source: {}", tweets.len(), source.name);
+
+    let newest = tweets
+        .iter()
+        .map(|t| t.id.as_str())
+        .chain(state.pending_since_id.as_deref())
+        .reduce(|a, b| if is_newer(b, a) { b } else { a })
+        .map(str::to_string);
+    if next_token.is_some() {
+        log::info!("Stopped after {} pages for source {}; the next run continues from there", max_pages, source.name);
+        state.pending_since_id = newest;
+    } else {
+        state.since_id = newest.or(state.since_id);
+        state.pending_since_id = None;
+    }
+    state.next_token = next_token;
+
+    let items: Vec<Item> = tweets
+        .iter()
+        .map(|tweet| tweet_to_item(tweet, &users, source))
+        .collect();
+    let inserted = super::store_items(pool, source, items).await;
+
+    if let Err(e) = crate::db::set_source_meta_key(pool, source.id, "twitter_state", &serde_json::to_value(&state)?).await {
+        log::warn!("Failed to store Twitter sync state for source {}: {}", source.name, e);
+    }
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+fn tweet_to_item(tweet: &Tweet, users: &HashMap<String, User>, source: &crate::models::Source) -> Item {
+    let author = tweet.author_id.as_ref().and_then(|id| users.get(id));
+    let handle = author.map(|a| a.username.clone());
+
+    let url = match &handle {
+        Some(handle) => format!("https://x.com/{}/status/{}", handle, tweet.id),
+        None => format!("https://x.com/i/web/status/{}", tweet.id),
+    };
+
+    // First line of the tweet, prefixed with the author, as the title
+    let text = tweet.text.trim();
+    let first_line = text.lines().next().unwrap_or_default();
+    let title = match &handle {
+        Some(handle) => format!("@{}: {}", handle, crate::html::truncate_chars(first_line, 120)),
+        None => crate::html::truncate_chars(first_line, 120),
+    };
+
+    let metadata = serde_json::json!({
+        "tweet_id": tweet.id,
+        "author_id": tweet.author_id,
+        "author_handle": handle,
+        "author_name": author.and_then(|a| a.name.clone()),
+        "metrics": tweet.public_metrics,
+        "lang": tweet.lang,
+        "conversation_id": tweet.conversation_id,
+        "referenced_tweets": tweet.referenced_tweets,
+    });
+
+    Item {
+        id: Uuid::new_v4(),
+        source_id: source.id,
+        source_type: Medium::Tweet.as_str().to_string(),
+        external_id: Some(tweet.id.clone()),
+        title,
+        url,
+        summary: Some(text.to_string()).filter(|t| !t.is_empty()),
+        body: None,
+        published_at: tweet.created_at.unwrap_or_else(Utc::now),
+        raw_metadata: metadata,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, Query};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+    use axum::{Json, Router};
+
+    /// Minimal stand-in for the v2 API: one user with three tweets, two per page
+    fn mock_api() -> Router {
+        async fn user(Path(handle): Path<String>) -> Json<serde_json::Value> {
+            Json(serde_json::json!({ "data": { "id": "42", "username": handle, "name": "Example" } }))
+        }
+
+        async fn tweets(
+            headers: HeaderMap,
+            Path(_user_id): Path<String>,
+            Query(params): Query<HashMap<String, String>>,
+        ) -> Result<Json<serde_json::Value>, StatusCode> {
+            if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Bearer test-token") {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+
+            let all = [("103", "third"), ("102", "second"), ("101", "first")];
+            let since = params.get("since_id").map(String::as_str).unwrap_or("0");
+            let newer: Vec<_> = all.iter().filter(|(id, _)| is_newer(id, since)).collect();
+            let start = if params.get("pagination_token").map(String::as_str) == Some("page2") { 2 } else { 0 };
+            let page: Vec<_> = newer.iter().skip(start).take(2).collect();
+
+            let data: Vec<_> = page
+                .iter()
+                .map(|(id, text)| serde_json::json!({
+                    "id": id,
+                    "text": text,
+                    "author_id": "42",
+                    "created_at": "2026-01-05T09:00:00.000Z",
+                    "public_metrics": { "like_count": 3, "retweet_count": 1 },
+                }))
+                .collect();
+            let next_token = (start == 0 && newer.len() > 2).then_some("page2");
+
+            Ok(Json(serde_json::json!({
+                "data": data,
+                "includes": { "users": [{ "id": "42", "username": "example", "name": "Example" }] },
+                "meta": { "result_count": data.len(), "next_token": next_token },
+            })))
+        }
+
+        Router::new()
+            .route("/2/users/by/username/{handle}", get(user))
+            .route("/2/users/{user_id}/tweets", get(tweets))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tweets_against_mock_api() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/2", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, mock_api()).await.unwrap() });
+
+        let client = TwitterClient::new(&base_url, "test-token").unwrap();
+        let user = client.user_by_handle("example").await.unwrap();
+        assert_eq!(user.id, "42");
+
+        let (tweets, users, next_token) = client.fetch_tweets("/users/42/tweets", None, None, true, None, 5).await.unwrap();
+        let ids: Vec<_> = tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["103", "102", "101"]);
+        assert_eq!(users["42"].username, "example");
+        assert_eq!(next_token, None);
+
+        let (tweets, _, _) = client.fetch_tweets("/users/42/tweets", None, Some("102"), true, None, 5).await.unwrap();
+        assert_eq!(tweets.len(), 1);
+
+        // A run cut short by max_pages hands back the token the next run resumes from
+        let (tweets, _, next_token) = client.fetch_tweets("/users/42/tweets", None, None, true, None, 1).await.unwrap();
+        assert_eq!(tweets.len(), 2);
+        assert_eq!(next_token.as_deref(), Some("page2"));
+        let (tweets, _, next_token) = client
+            .fetch_tweets("/users/42/tweets", None, None, true, next_token.as_deref(), 1)
+            .await
+            .unwrap();
+        assert_eq!(tweets.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["101"]);
+        assert_eq!(next_token, None);
+
+        let unauthorized = TwitterClient::new(&base_url, "wrong").unwrap();
+        assert!(unauthorized.fetch_tweets("/users/42/tweets", None, None, true, None, 1).await.is_err());
+    }
+
+    #[test]
+    fn test_tweet_to_item() {
+        let tweet: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "1800000000000000001",
+            "text": "New model release\nDetails in thread",
+            "author_id": "42",
+            "created_at": "2026-01-05T09:00:00.000Z",
+            "public_metrics": { "like_count": 10 },
+        }))
+        .unwrap();
+        let users = HashMap::from([(
+            "42".to_string(),
+            User { id: "42".to_string(), username: "example".to_string(), name: None },
+        )]);
+        let source = crate::models::Source::test("twitter_api", "tweet", serde_json::json!({ "mode": "user_timeline", "handle": "example" }));
+
+        let item = tweet_to_item(&tweet, &users, &source);
+        assert_eq!(item.title, "@example: New model release");
+        assert_eq!(item.url, "https://x.com/example/status/1800000000000000001");
+        assert_eq!(item.external_id.as_deref(), Some("1800000000000000001"));
+        assert_eq!(item.source_type, "tweet");
+        assert_eq!(item.raw_metadata["author_handle"], "example");
+        assert_eq!(item.raw_metadata["metrics"]["like_count"], 10);
+    }
+
+    #[test]
+    fn test_timeline_from_meta() {
+        assert_eq!(
+            Timeline::from_meta(&serde_json::json!({ "mode": "user_timeline", "handle": "@karpathy" })).unwrap(),
+            Timeline::UserTweets { handle: "karpathy".to_string() }
+        );
+        assert!(Timeline::from_meta(&serde_json::json!({ "mode": "list" })).is_err());
+        assert!(is_newer("1000", "999"));
+        assert!(!is_newer("999", "1000"));
+    }
+}
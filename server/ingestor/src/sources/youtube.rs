@@ -0,0 +1,369 @@
+//! YouTube ingestion for `youtube` sources through the per-channel / per-playlist Atom feeds
+//!
+//! `meta` names what to follow: `{"channel_id": "UCXZCJLdBC09xxGZ6gcdrc6A"}` or
+//! `{"playlist_id": "PL..."}`; a full feed URL in `ingest_url` works too.
+//! Descriptions, thumbnails and view counts come from the `media:group` of each entry.
+//! With `"captions": true` the caption track (`caption_language`, default `en`; uploaded
+//! captions before auto-generated ones) is stored as the item body and kept across cycles.
+
+use crate::html::{html_to_text, truncate_chars};
+use crate::models::{Item, Medium};
+use anyhow::{anyhow, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const FEED_URL: &str = "https://www.youtube.com/feeds/videos.xml";
+const WATCH_URL: &str = "https://www.youtube.com/watch";
+
+/// Transcripts are longer than feed bodies; keep the same ceiling as full-text articles
+const MAX_TRANSCRIPT_CHARS: usize = 50000;
+
+/// Where the videos come from and whether to pull captions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelOptions {
+    pub feed_url: String,
+    pub captions: bool,
+    pub caption_language: String,
+}
+
+impl ChannelOptions {
+    pub fn from_source(source: &crate::models::Source) -> Result<Self> {
+        let meta = &source.meta;
+        let text = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        let feed_url = if let Some(channel_id) = text("channel_id") {
+            format!("{}?channel_id={}", FEED_URL, urlencoding::encode(&channel_id))
+        } else if let Some(playlist_id) = text("playlist_id") {
+            format!("{}?playlist_id={}", FEED_URL, urlencoding::encode(&playlist_id))
+        } else {
+            source
+                .ingest_url
+                .clone()
+                .filter(|url| !url.trim().is_empty())
+                .ok_or_else(|| anyhow!("meta.channel_id, meta.playlist_id or ingest_url is required"))?
+        };
+
+        Ok(Self {
+            feed_url,
+            captions: meta.get("captions").and_then(|v| v.as_bool()).unwrap_or(false),
+            caption_language: text("caption_language").unwrap_or_else(|| "en".to_string()),
+        })
+    }
+}
+
+/// One entry of `captionTracks` in the watch page's player response
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptionTrack {
+    pub base_url: String,
+    pub language_code: String,
+    /// `asr` for auto-generated captions
+    pub kind: Option<String>,
+}
+
+pub async fn run_youtube_ingestion(pool: &PgPool, source: &crate::models::Source) -> Result<u64> {
+    let options = ChannelOptions::from_source(source)
+        .map_err(|e| anyhow!("Invalid youtube source {}: {}", source.name, e))?;
+
+    log::info!("Starting YouTube ingestion for source: {} ({})", source.name, options.feed_url);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .user_agent(crate::http::USER_AGENT)
+        .build()?;
+
+    let content = client
+        .get(&options.feed_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let feed = feed_rs::parser::parse(&content[..])?;
+
+    let mut items: Vec<Item> = feed
+        .entries
+        .into_iter()
+        .filter_map(|entry| super::rss::entry_to_item(entry, source, &HashMap::new()))
+        .map(video_item)
+        .collect();
+
+    log::info!("Fetched {} videos for source: {}", items.len(), source.name);
+
+    if options.captions {
+        for item in items.iter_mut() {
+            attach_transcript(pool, &client, item, &options.caption_language).await;
+        }
+    }
+
+    let inserted = super::store_items(pool, source, items).await;
+
+    log::info!("Successfully inserted/updated {} items from source: {}", inserted, source.name);
+
+    Ok(inserted)
+}
+
+/// YouTube Atom entries carry no summary; use the media description, and key items by video id
+fn video_item(mut item: Item) -> Item {
+    item.source_type = Medium::Video.as_str().to_string();
+
+    let description = item.raw_metadata["media"]["description"].as_str().map(str::to_string);
+    if item.summary.is_none() {
+        item.summary = description.filter(|d| !d.is_empty()).map(|d| truncate_chars(&d, 500));
+    }
+
+    // Atom ids look like `yt:video:<id>`
+    let video_id = item
+        .external_id
+        .as_deref()
+        .and_then(|id| id.strip_prefix("yt:video:"))
+        .map(str::to_string);
+    if let (Some(video_id), Some(obj)) = (video_id, item.raw_metadata.as_object_mut()) {
+        obj.insert("video_id".to_string(), serde_json::json!(video_id));
+    }
+
+    item
+}
+
+/// Put the caption text in `body`, reusing the transcript stored in an earlier cycle
+async fn attach_transcript(pool: &PgPool, client: &reqwest::Client, item: &mut Item, language: &str) {
+    match crate::db::get_transcript(pool, item.source_id, item.external_id.as_deref(), &item.url).await {
+        Ok(Some((body, transcript))) => {
+            apply_transcript(item, body, transcript);
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to look up stored transcript for {}: {}", item.url, e),
+    }
+
+    let Some(video_id) = item.raw_metadata["video_id"].as_str().map(str::to_string) else {
+        return;
+    };
+
+    match fetch_transcript(client, &video_id, language).await {
+        Ok(Some((text, track))) => {
+            let transcript = serde_json::json!({
+                "language": track.language_code,
+                "auto_generated": track.kind.as_deref() == Some("asr"),
+                "word_count": text.split_whitespace().count(),
+            });
+            apply_transcript(item, transcript_body(&text), transcript);
+        }
+        Ok(None) => log::debug!("No {} captions for video {}", language, video_id),
+        Err(e) => log::warn!("Failed to fetch captions for video {}: {}", video_id, e),
+    }
+}
+
+/// Caption text as an HTML body; `store_items` tags topics from its text
+fn transcript_body(text: &str) -> String {
+    format!("<p>{}</p>", escape_html(&truncate_chars(text, MAX_TRANSCRIPT_CHARS)))
+}
+
+fn apply_transcript(item: &mut Item, body: String, transcript: serde_json::Value) {
+    item.body = Some(body);
+    if let Some(obj) = item.raw_metadata.as_object_mut() {
+        obj.insert("transcript".to_string(), transcript);
+    }
+}
+
+async fn fetch_transcript(
+    client: &reqwest::Client,
+    video_id: &str,
+    language: &str,
+) -> Result<Option<(String, CaptionTrack)>> {
+    let page = client
+        .get(WATCH_URL)
+        .query(&[("v", video_id)])
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let Some(track) = pick_track(caption_tracks(&page), language) else {
+        return Ok(None);
+    };
+
+    let captions = client
+        .get(&track.base_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let text = captions_to_text(&captions);
+    Ok(Some((text, track)).filter(|(text, _)| !text.is_empty()))
+}
+
+/// Pull the `captionTracks` array out of the watch page's embedded player response
+pub fn caption_tracks(page: &str) -> Vec<CaptionTrack> {
+    let Some(start) = page.find("\"captionTracks\":").map(|i| i + "\"captionTracks\":".len()) else {
+        return Vec::new();
+    };
+
+    // The array ends at the matching bracket (brackets inside strings are escaped or absent)
+    let mut depth = 0;
+    let mut end = None;
+    for (i, c) in page[start..].char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(start + i + 1);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    end.and_then(|end| serde_json::from_str(&page[start..end]).ok())
+        .unwrap_or_default()
+}
+
+/// Uploaded captions in the requested language win over auto-generated ones
+pub fn pick_track(tracks: Vec<CaptionTrack>, language: &str) -> Option<CaptionTrack> {
+    let matches_language = |track: &CaptionTrack| {
+        track.language_code == language || track.language_code.starts_with(&format!("{}-", language))
+    };
+
+    let (uploaded, generated): (Vec<_>, Vec<_>) = tracks
+        .into_iter()
+        .filter(matches_language)
+        .partition(|track| track.kind.as_deref() != Some("asr"));
+
+    uploaded.into_iter().next().or_else(|| generated.into_iter().next())
+}
+
+/// Flatten a timedtext document (`<text>` cues, or `<p>` cues in the srv3 format) into plain text
+pub fn captions_to_text(xml: &str) -> String {
+    let mut reader = Reader::from_str(xml);
+    let mut cues = Vec::new();
+    let mut current = String::new();
+    let mut in_cue = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if matches!(e.name().as_ref(), b"text" | b"p") => {
+                in_cue = true;
+                current.clear();
+            }
+            Ok(Event::Text(text)) if in_cue => {
+                if let Ok(text) = text.unescape() {
+                    current.push_str(&text);
+                }
+            }
+            Ok(Event::End(e)) if matches!(e.name().as_ref(), b"text" | b"p") => {
+                in_cue = false;
+                // Cue text is HTML-escaped a second time (`&amp;#39;`)
+                let cue = html_to_text(&current).split_whitespace().collect::<Vec<_>>().join(" ");
+                if !cue.is_empty() {
+                    cues.push(cue);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                log::debug!("Stopped reading captions: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    cues.join(" ")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns:yt="http://www.youtube.com/xml/schemas/2015" xmlns:media="http://search.yahoo.com/mrss/" xmlns="http://www.w3.org/2005/Atom">
+ <title>Lab Talks</title>
+ <entry>
+  <id>yt:video:dQw4w9WgXcQ</id>
+  <yt:videoId>dQw4w9WgXcQ</yt:videoId>
+  <title>Scaling laws, revisited</title>
+  <link rel="alternate" href="https://www.youtube.com/watch?v=dQw4w9WgXcQ"/>
+  <author><name>Lab Talks</name></author>
+  <published>2026-01-05T09:00:00+00:00</published>
+  <media:group>
+   <media:title>Scaling laws, revisited</media:title>
+   <media:content url="https://www.youtube.com/v/dQw4w9WgXcQ?version=3" type="application/x-shockwave-flash" width="640" height="390"/>
+   <media:thumbnail url="https://i1.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg" width="480" height="360"/>
+   <media:description>A talk on compute-optimal training.</media:description>
+   <media:community>
+    <media:starRating count="120" average="5.00" min="1" max="5"/>
+    <media:statistics views="4321"/>
+   </media:community>
+  </media:group>
+ </entry>
+</feed>"#;
+
+    fn source() -> crate::models::Source {
+        crate::models::Source::test("youtube", "video", serde_json::json!({ "channel_id": "UC123" }))
+    }
+
+    #[test]
+    fn test_video_item_from_feed() {
+        let feed = feed_rs::parser::parse(FEED.as_bytes()).unwrap();
+        let entry = feed.entries.into_iter().next().unwrap();
+        let item = video_item(super::super::rss::entry_to_item(entry, &source(), &HashMap::new()).unwrap());
+
+        assert_eq!(item.source_type, "video");
+        assert_eq!(item.url, "https://www.youtube.com/watch?v=dQw4w9WgXcQ");
+        assert_eq!(item.summary.as_deref(), Some("A talk on compute-optimal training."));
+        assert_eq!(item.raw_metadata["video_id"], "dQw4w9WgXcQ");
+        assert_eq!(item.raw_metadata["media"]["views"], 4321);
+        assert_eq!(item.raw_metadata["media"]["thumbnail_url"], "https://i1.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg");
+
+        let options = ChannelOptions::from_source(&source()).unwrap();
+        assert_eq!(options.feed_url, "https://www.youtube.com/feeds/videos.xml?channel_id=UC123");
+        assert!(!options.captions);
+    }
+
+    #[test]
+    fn test_caption_tracks() {
+        let page = r#"<script>var ytInitialPlayerResponse = {"captions":{"playerCaptionsTracklistRenderer":{"captionTracks":[{"baseUrl":"https://www.youtube.com/api/timedtext?v=x&lang=en&kind=asr","name":{"simpleText":"English (auto-generated)"},"languageCode":"en","kind":"asr"},{"baseUrl":"https://www.youtube.com/api/timedtext?v=x&lang=en-GB","name":{"simpleText":"English (UK)"},"languageCode":"en-GB"}],"audioTracks":[]}}};</script>"#;
+
+        let tracks = caption_tracks(page);
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].base_url, "https://www.youtube.com/api/timedtext?v=x&lang=en&kind=asr");
+
+        let track = pick_track(tracks, "en").unwrap();
+        assert_eq!(track.language_code, "en-GB");
+        assert!(pick_track(caption_tracks(page), "de").is_none());
+        assert!(caption_tracks("<html></html>").is_empty());
+    }
+
+    #[test]
+    fn test_captions_to_text() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8" ?><transcript><text start="0.1" dur="2.0">Today we&amp;#39;ll talk</text><text start="2.1" dur="1.5">about   scaling
+laws.</text></transcript>"#;
+        assert_eq!(captions_to_text(xml), "Today we'll talk about scaling laws.");
+
+        let srv3 = r#"<timedtext format="3"><body><p t="0" d="1000">Hello</p><p t="1000" d="900">world</p></body></timedtext>"#;
+        assert_eq!(captions_to_text(srv3), "Hello world");
+    }
+
+    #[test]
+    fn test_transcript_body_round_trips_to_text() {
+        let body = transcript_body("Today we compare RLHF & DPO <live>");
+        assert_eq!(crate::html::html_to_text(&body), "Today we compare RLHF & DPO <live>");
+    }
+}
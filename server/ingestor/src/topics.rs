@@ -1,254 +1,277 @@
-/// Simple keyword-based topic extraction for AI-related content
-/// Topics are detected based on title and summary text
-
-pub fn extract_topics(title: &str, summary: Option<&str>) -> Vec<String> {
-    let combined = format!(
-        "{} {}",
-        title,
-        summary.unwrap_or(""),
-
-    );
-    let text_lower = combined.to_lowercase();
-
-    let mut topics = Vec::new();
-
-    // LLM / Language Models
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "llm",
-            "large language model",
-            "gpt",
-            "transformer",
-            "bert",
-            "t5",
-            "llama",
-            "claude",
-            "chatgpt",
-            "prompt engineering",
-        ],
-    ) {
-        topics.push("LLM".to_string());
-    }
+//! Simple keyword-based topic extraction for AI-related content
+//! Topics are detected based on title, summary and body text, using the taxonomy in the `topics`
+//! table (the same rows the web /topics page shows). It is loaded at startup and reloaded
+//! before each daemon cycle, so taxonomy edits don't need a redeploy. Until the table has
+//! been read (or when it can't be, or has no active topics) `DEFAULT_TOPICS`, the
+//! same topics the table's migration seeds, are used.
 
-    // Reinforcement Learning
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "reinforcement learning",
-            "rl",
-            "rlhf",
-            "reward model",
-            "policy gradient",
-            "q-learning",
-            "dpo",
-        ],
-    ) {
-        topics.push("RL".to_string());
-    }
+use crate::models::Topic;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use sqlx::PgPool;
+use std::sync::{Arc, OnceLock, RwLock};
 
-    // Multimodal / Vision
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "multimodal",
-            "vision",
-            "image",
-            "video",
-            "dall-e",
-            "clip",
-            "visual",
-            "ocr",
-            "object detection",
-        ],
-    ) {
-        topics.push("Multimodal".to_string());
-    }
+/// Topics with their lowercased keywords and aliases, in taxonomy order
+#[derive(Debug, Clone, Default)]
+pub struct Taxonomy {
+    rules: Vec<TopicRule>,
+}
 
-    // Systems / Infrastructure / MLOps
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "infrastructure",
-            "mlops",
-            "systems",
-            "deployment",
-            "production",
-            "scalability",
-            "distributed",
-            "gpu",
-            "vram",
-            "quantization",
-            "inference",
-            "serving",
-        ],
-    ) {
-        topics.push("Systems".to_string());
-    }
+#[derive(Debug, Clone)]
+struct TopicRule {
+    name: String,
+    /// Any keyword as a whole word or phrase (plural `s`/`es` allowed), so "rl" doesn't match "world"
+    pattern: Option<Regex>,
+}
 
-    // Alignment / Safety / Ethics
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "alignment",
-            "safety",
-            "ethics",
-            "fairness",
-            "bias",
-            "hallucination",
-            "interpretability",
-            "explainability",
-            "responsible ai",
-            "agi",
-        ],
-    ) {
-        topics.push("Alignment".to_string());
+impl TopicRule {
+    fn new(name: String, keywords: &[String]) -> Self {
+        let alternatives: Vec<String> = keywords.iter().map(|keyword| regex::escape(keyword)).collect();
+        let pattern = (!alternatives.is_empty())
+            .then(|| Regex::new(&format!(r"\b(?:{})(?:e?s)?\b", alternatives.join("|"))).ok())
+            .flatten();
+        Self { name, pattern }
     }
 
-    // Agents / Autonomous Systems
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "agent",
-            "autonomous",
-            "robotics",
-            "automation",
-            "action planning",
-            "tool use",
-            "function calling",
-        ],
-    ) {
-        topics.push("Agents".to_string());
+    fn matches(&self, text: &str) -> bool {
+        self.pattern.as_ref().is_some_and(|pattern| pattern.is_match(text))
     }
+}
 
-    // Finance / Markets
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "finance",
-            "trading",
-            "market",
-            "stock",
-            "portfolio",
-            "investment",
-            "risk",
-            "quant",
-            "algorithmic",
-        ],
-    ) {
-        topics.push("Finance".to_string());
+impl Taxonomy {
+    pub fn new(topics: &[Topic]) -> Self {
+        let rules = topics
+            .iter()
+            .filter(|topic| topic.active)
+            .map(|topic| {
+                // An alias is just another phrase that names the topic
+                let keywords: Vec<String> = topic
+                    .keywords
+                    .iter()
+                    .chain(&topic.aliases)
+                    .map(|keyword| keyword.trim().to_lowercase())
+                    .filter(|keyword| !keyword.is_empty())
+                    .collect();
+                TopicRule::new(topic.name.clone(), &keywords)
+            })
+            .collect();
+        Self { rules }
     }
 
-    // Open Source / Community
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "open source",
-            "hugging face",
-            "pytorch",
-            "tensorflow",
-            "community",
-            "huggingface",
-        ],
-    ) {
-        topics.push("Open Source".to_string());
+    /// The default topics (`DEFAULT_TOPICS`)
+    pub fn seeded() -> Self {
+        let topics: Vec<Topic> = DEFAULT_TOPICS
+            .iter()
+            .zip(1..)
+            .map(|(topic, position)| Topic {
+                id: 0,
+                name: topic.name.to_string(),
+                description: Some(topic.description.to_string()),
+                color: topic.color.to_string(),
+                aliases: topic.aliases.iter().map(|s| s.to_string()).collect(),
+                keywords: topic.keywords.iter().map(|s| s.to_string()).collect(),
+                position,
+                active: true,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            })
+            .collect();
+        Self::new(&topics)
     }
 
-    // Search / Retrieval / Knowledge
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "retrieval",
-            "rag",
-            "search",
-            "knowledge base",
-            "vector database",
-            "embedding",
-            "semantic search",
-        ],
-    ) {
-        topics.push("Search".to_string());
+    pub fn len(&self) -> usize {
+        self.rules.len()
     }
 
-    // Data / Datasets
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "dataset",
-            "data",
-            "annotation",
-            "labeling",
-            "synthetic data",
-            "pretraining",
-        ],
-    ) {
-        topics.push("Data".to_string());
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
     }
 
-    // Optimization / Training
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "optimization",
-            "training",
-            "fine-tuning",
-            "finetuning",
-            "learning rate",
-            "gradient",
-            "loss",
-        ],
-    ) {
-        topics.push("Optimization".to_string());
+    /// `body` is plain text (full article, transcript), not HTML
+    pub fn extract(&self, title: &str, summary: Option<&str>, body: Option<&str>) -> Vec<String> {
+        let text_lower = format!("{} {} {}", title, summary.unwrap_or(""), body.unwrap_or("")).to_lowercase();
+
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(&text_lower))
+            .map(|rule| rule.name.clone())
+            .collect()
     }
+}
+
+/// A topic of the default taxonomy
+pub struct DefaultTopic {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub color: &'static str,
+    pub aliases: &'static [&'static str],
+    pub keywords: &'static [&'static str],
+}
 
-    // Applications / Use Cases
-    if contains_any_keyword(
-        &text_lower,
-        &[
-            "application",
-            "use case",
-            "product",
-            "tool",
-            "plugin",
-            "extension",
-            "integration",
-        ],
-    ) {
-        topics.push("Applications".to_string());
+/// The default taxonomy, in position order. The `topics` migration seeds exactly these rows
+/// (`test_migration_seeds_default_topics` renders the `INSERT` from this list and checks it).
+pub const DEFAULT_TOPICS: &[DefaultTopic] = &[
+    DefaultTopic {
+        name: "LLM",
+        description: "Large language models, prompting and chat assistants",
+        color: "blue",
+        aliases: &["large language models", "language models"],
+        keywords: &["llm", "large language model", "gpt", "transformer", "bert", "t5", "llama", "claude", "chatgpt", "prompt engineering"],
+    },
+    DefaultTopic {
+        name: "RL",
+        description: "Reinforcement learning, reward modelling and preference optimisation",
+        color: "orange",
+        aliases: &["reinforcement learning"],
+        keywords: &["reinforcement learning", "rl", "rlhf", "reward model", "policy gradient", "q-learning", "dpo"],
+    },
+    DefaultTopic {
+        name: "Multimodal",
+        description: "Vision, image, video and other non-text modalities",
+        color: "pink",
+        aliases: &["vision", "computer vision"],
+        keywords: &["multimodal", "vision", "image", "video", "dall-e", "clip", "visual", "ocr", "object detection"],
+    },
+    DefaultTopic {
+        name: "Systems",
+        description: "Infrastructure, inference, serving and MLOps",
+        color: "gray",
+        aliases: &["infrastructure", "mlops"],
+        keywords: &["infrastructure", "mlops", "systems", "deployment", "production", "scalability", "distributed", "gpu", "vram", "quantization", "inference", "serving"],
+    },
+    DefaultTopic {
+        name: "Alignment",
+        description: "Safety, interpretability, ethics and bias",
+        color: "red",
+        aliases: &["ai safety", "safety"],
+        keywords: &["alignment", "safety", "ethics", "fairness", "bias", "hallucination", "interpretability", "explainability", "responsible ai", "agi"],
+    },
+    DefaultTopic {
+        name: "Agents",
+        description: "Autonomous agents, tool use and robotics",
+        color: "purple",
+        aliases: &["ai agents", "autonomous agents"],
+        keywords: &["agent", "autonomous", "robotics", "automation", "action planning", "tool use", "function calling"],
+    },
+    DefaultTopic {
+        name: "Finance",
+        description: "Markets, trading and quantitative finance",
+        color: "green",
+        aliases: &["markets", "quant finance"],
+        keywords: &["finance", "trading", "market", "stock", "portfolio", "investment", "risk", "quant", "algorithmic"],
+    },
+    DefaultTopic {
+        name: "Open Source",
+        description: "Open models, libraries and the community around them",
+        color: "teal",
+        aliases: &["oss", "open-source"],
+        keywords: &["open source", "hugging face", "pytorch", "tensorflow", "community", "huggingface"],
+    },
+    DefaultTopic {
+        name: "Search",
+        description: "Retrieval, RAG, embeddings and vector search",
+        color: "cyan",
+        aliases: &["retrieval", "rag"],
+        keywords: &["retrieval", "rag", "search", "knowledge base", "vector database", "embedding", "semantic search"],
+    },
+    DefaultTopic {
+        name: "Data",
+        description: "Datasets, annotation and synthetic data",
+        color: "yellow",
+        aliases: &["datasets"],
+        keywords: &["dataset", "data", "annotation", "labeling", "synthetic data", "pretraining"],
+    },
+    DefaultTopic {
+        name: "Optimization",
+        description: "Training, fine-tuning and optimisation methods",
+        color: "messenger",
+        aliases: &["training", "fine-tuning"],
+        keywords: &["optimization", "training", "fine-tuning", "finetuning", "learning rate", "gradient", "loss"],
+    },
+    DefaultTopic {
+        name: "Applications",
+        description: "Products, tools and real-world use cases",
+        color: "linkedin",
+        aliases: &["use cases", "products"],
+        keywords: &["application", "use case", "product", "tool", "plugin", "extension", "integration"],
+    },
+];
+
+fn taxonomy() -> &'static RwLock<Arc<Taxonomy>> {
+    static TAXONOMY: OnceLock<RwLock<Arc<Taxonomy>>> = OnceLock::new();
+    TAXONOMY.get_or_init(|| RwLock::new(Arc::new(Taxonomy::seeded())))
+}
+
+/// Topics currently used for tagging
+pub fn current_len() -> usize {
+    taxonomy().read().unwrap_or_else(|e| e.into_inner()).len()
+}
+
+/// Replace the taxonomy with the active rows of the `topics` table.
+/// On error, or when no topic is active, the taxonomy in use (seeded or last loaded) is kept.
+pub async fn load(pool: &PgPool) -> Result<usize> {
+    let topics = crate::db::get_active_topics(pool).await?;
+    let loaded = Taxonomy::new(&topics);
+    let count = loaded.len();
+    if loaded.is_empty() {
+        return Err(anyhow!("The topics table has no active topics"));
     }
 
-    topics
+    *taxonomy().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(loaded);
+    Ok(count)
 }
 
-/// Check if any of the keywords are contained in the text
-fn contains_any_keyword(text: &str, keywords: &[&str]) -> bool {
-    keywords.iter().any(|keyword| {
-        // Match whole words or word boundaries
-        text.contains(keyword)
-    })
+pub fn extract_topics(title: &str, summary: Option<&str>, body: Option<&str>) -> Vec<String> {
+    let current = taxonomy().read().unwrap_or_else(|e| e.into_inner()).clone();
+    current.extract(title, summary, body)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Utc;
+
+    fn topic(name: &str, keywords: &[&str], aliases: &[&str]) -> Topic {
+        Topic {
+            id: 0,
+            name: name.to_string(),
+            description: None,
+            color: "gray".to_string(),
+            aliases: aliases.iter().map(|s| s.to_string()).collect(),
+            keywords: keywords.iter().map(|s| s.to_string()).collect(),
+            position: 0,
+            active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn taxonomy() -> Taxonomy {
+        Taxonomy::new(&[
+            topic("LLM", &["llm", "large language model", "gpt", "transformer"], &["language models"]),
+            topic("RL", &["reinforcement learning", "rlhf", "dpo"], &[]),
+            topic("Multimodal", &["multimodal", "vision", "image", "dall-e"], &[]),
+        ])
+    }
 
     #[test]
     fn test_extract_llm_topics() {
-        let topics = extract_topics("GPT-4 Large Language Models", Some("A new LLM model"));
+        let topics = taxonomy().extract("GPT-4 Large Language Models", Some("A new LLM model"), None);
         assert!(topics.contains(&"LLM".to_string()));
     }
 
     #[test]
     fn test_extract_multimodal() {
-        let topics = extract_topics("DALL-E: Multimodal Image Generation", Some("Vision and text"));
+        let topics = taxonomy().extract("DALL-E: Multimodal Image Generation", Some("Vision and text"), None);
         assert!(topics.contains(&"Multimodal".to_string()));
     }
 
     #[test]
     fn test_extract_multiple_topics() {
-        let topics = extract_topics(
+        let topics = taxonomy().extract(
             "Fine-tuning a RLHF model with GPT",
             Some("Reinforcement learning and LLM training"),
+            None,
         );
         assert!(topics.contains(&"LLM".to_string()));
         assert!(topics.contains(&"RL".to_string()));
@@ -256,7 +279,78 @@ mod tests {
 
     #[test]
     fn test_extract_no_topics() {
-        let topics = extract_topics("Random article about cooking", None);
+        let topics = taxonomy().extract("Random article about cooking", None, None);
         assert!(topics.is_empty());
     }
+
+    #[test]
+    fn test_extract_from_body() {
+        let topics = taxonomy().extract("Episode 12", None, Some("Today we talk about RLHF and DPO."));
+        assert_eq!(topics, vec!["RL".to_string()]);
+    }
+
+    /// `INSERT` statement seeding `DEFAULT_TOPICS` into the `topics` table
+    fn seed_sql() -> String {
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+        let array = |items: &[&str]| format!("ARRAY[{}]", items.iter().map(|s| quote(s)).collect::<Vec<_>>().join(", "));
+        let rows: Vec<String> = DEFAULT_TOPICS
+            .iter()
+            .zip(1..)
+            .map(|(topic, position)| {
+                format!(
+                    "    ({}, {}, {},\n     {},\n     {}, {})",
+                    quote(topic.name),
+                    quote(topic.description),
+                    quote(topic.color),
+                    array(topic.aliases),
+                    array(topic.keywords),
+                    position
+                )
+            })
+            .collect();
+        format!(
+            "INSERT INTO topics (name, description, color, aliases, keywords, position) VALUES\n{}\nON CONFLICT (name) DO NOTHING;",
+            rows.join(",\n")
+        )
+    }
+
+    #[test]
+    fn test_migration_seeds_default_topics() {
+        let migration = include_str!("../../migrations/20260217000023_add_topics_table.sql");
+        assert!(migration.contains(&seed_sql()));
+    }
+
+    #[test]
+    fn test_seeded_topics() {
+        let seeded = Taxonomy::seeded();
+        assert_eq!(seeded.len(), 12);
+        assert_eq!(seeded.rules[0].name, "LLM");
+        assert!(seeded.rules[0].matches("prompt engineering"));
+        assert!(seeded.rules[0].matches("language models"));
+        assert_eq!(seeded.rules[11].name, "Applications");
+        assert_eq!(seeded.extract("Inference serving on one GPU", None, None), vec!["Systems".to_string()]);
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        let topics = taxonomy().extract(
+            "Around the world",
+            None,
+            Some("Cold storage for imagery, a revision of the envisioned plan"),
+        );
+        assert!(topics.is_empty());
+
+        let topics = taxonomy().extract("Transformers and LLMs", None, Some("Large images, in vision-language work"));
+        assert_eq!(topics, vec!["LLM".to_string(), "Multimodal".to_string()]);
+    }
+
+    #[test]
+    fn test_aliases_and_inactive_topics() {
+        let mut cooking = topic("Cooking", &["cooking"], &[]);
+        cooking.active = false;
+        let taxonomy = Taxonomy::new(&[cooking, topic("Alignment", &["interpretability"], &["AI Safety"])]);
+
+        assert_eq!(taxonomy.len(), 1);
+        assert_eq!(taxonomy.extract("Notes on ai safety and cooking", None, None), vec!["Alignment".to_string()]);
+    }
 }
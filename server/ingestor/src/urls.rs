@@ -0,0 +1,214 @@
+//! URL canonicalization so the same article always maps to the same `items.url`
+//! Stored URLs only lose tracking parameters, fragments, default ports and stray slashes;
+//! `http`/`https` and `www.` variants are only folded together in the comparison key (`url_key`)
+
+use anyhow::Result;
+use scraper::{Html, Selector};
+use std::time::Duration;
+use url::{form_urlencoded, Url};
+
+/// Query parameters that only carry campaign/click tracking
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid",
+    "gclid",
+    "dclid",
+    "msclkid",
+    "mc_cid",
+    "mc_eid",
+    "igshid",
+    "mkt_tok",
+    "_hsenc",
+    "_hsmi",
+    "ref_src",
+    "ref_url",
+];
+
+/// Hosts that only redirect to the real article
+const REDIRECT_HOSTS: &[&str] = &[
+    "feeds.feedburner.com",
+    "feedproxy.google.com",
+    "feedburner.google.com",
+];
+
+fn is_tracking_param(name: &str) -> bool {
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name)
+}
+
+/// Canonicalize a URL. Returns the input unchanged if it can't be parsed.
+pub fn canonicalize_url(raw: &str) -> String {
+    let mut url = match Url::parse(raw.trim()) {
+        Ok(url) => url,
+        Err(_) => return raw.to_string(),
+    };
+
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return url.to_string();
+    }
+
+    // Drop explicit default ports (Url already omits the scheme's own); it also lowercases the host
+    if url.port() == Some(80) || url.port() == Some(443) {
+        let _ = url.set_port(None);
+    }
+
+    // Remove tracking parameters; the other pairs keep their order and original encoding
+    if let Some(query) = url.query().map(str::to_string) {
+        let pairs: Vec<&str> = query.split('&').collect();
+        let kept: Vec<&str> = pairs
+            .iter()
+            .copied()
+            .filter(|pair| {
+                let name = form_urlencoded::parse(pair.as_bytes()).next().map(|(name, _)| name);
+                !name.is_some_and(|name| is_tracking_param(&name))
+            })
+            .collect();
+        if kept.len() < pairs.len() {
+            let kept = kept.into_iter().filter(|pair| !pair.is_empty()).collect::<Vec<_>>();
+            url.set_query((!kept.is_empty()).then(|| kept.join("&")).as_deref());
+        }
+    }
+
+    url.set_fragment(None);
+
+    // Collapse leading duplicate slashes and drop the trailing slash (except for the root).
+    // Slashes further in are kept: they may belong to an embedded URL (web.archive.org/web/…/https://…)
+    let mut path = format!("/{}", url.path().trim_start_matches('/'));
+    if path.len() > 1 && path.ends_with('/') {
+        path.pop();
+    }
+    url.set_path(&path);
+
+    url.to_string()
+}
+
+/// SQL expression computing `url_key` from a stored (canonicalized) `items.url`.
+/// Backed by the `idx_items_url_key` expression index.
+pub const URL_KEY_SQL: &str = "regexp_replace(url, '^https?://(www\\.)?', '')";
+
+/// Comparison key for deduplication: the canonical URL without scheme and `www.`, so
+/// `http://www.example.com/a` and `https://example.com/a` are the same article.
+/// Mirrors `URL_KEY_SQL`.
+pub fn url_key(raw: &str) -> String {
+    let canonical = canonicalize_url(raw);
+    let rest = canonical
+        .strip_prefix("https://")
+        .or_else(|| canonical.strip_prefix("http://"));
+    match rest {
+        Some(rest) => rest.strip_prefix("www.").unwrap_or(rest).to_string(),
+        None => canonical,
+    }
+}
+
+/// Whether a URL points at a known redirector (e.g. FeedBurner)
+pub fn is_redirect_url(raw: &str) -> bool {
+    Url::parse(raw)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| REDIRECT_HOSTS.contains(&host)))
+        .unwrap_or(false)
+}
+
+/// Follow redirects and honour `<link rel="canonical">` to find the article's real URL.
+/// The result is canonicalized.
+pub async fn resolve_canonical_url(client: &reqwest::Client, raw: &str) -> Result<String> {
+    let response = client
+        .get(raw)
+        .header("User-Agent", "AI-Dashboard-Ingestor/0.1")
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let final_url = response.url().clone();
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.contains("html"))
+        .unwrap_or(false);
+
+    if is_html {
+        let html = response.text().await?;
+        if let Some(canonical) = find_canonical_link(&html, &final_url) {
+            return Ok(canonicalize_url(&canonical));
+        }
+    }
+
+    Ok(canonicalize_url(final_url.as_str()))
+}
+
+/// Extract `<link rel="canonical" href="...">` from a page, resolved against the page URL
+pub fn find_canonical_link(html: &str, base: &Url) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(r#"link[rel="canonical"]"#).ok()?;
+
+    document
+        .select(&selector)
+        .filter_map(|el| el.value().attr("href"))
+        .find_map(|href| base.join(href.trim()).ok())
+        .map(|url| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_tracking_params() {
+        assert_eq!(
+            canonicalize_url("https://example.com/post?utm_source=rss&utm_medium=feed&id=7&fbclid=abc"),
+            "https://example.com/post?id=7"
+        );
+    }
+
+    #[test]
+    fn test_normalizes_host_and_path() {
+        assert_eq!(
+            canonicalize_url("http://WWW.Example.com:80//blog/post/#comments"),
+            "http://www.example.com/blog/post"
+        );
+        assert_eq!(canonicalize_url("http://example.com:8080/a"), "http://example.com:8080/a");
+        assert_eq!(
+            canonicalize_url("https://github.com/org/repo/compare?ref=main"),
+            "https://github.com/org/repo/compare?ref=main"
+        );
+    }
+
+    #[test]
+    fn test_keeps_query_and_embedded_urls_intact() {
+        assert_eq!(canonicalize_url("https://example.com/search?foo&q=a%20b"), "https://example.com/search?foo&q=a%20b");
+        assert_eq!(
+            canonicalize_url("https://example.com/search?q=a%20b&utm_source=rss"),
+            "https://example.com/search?q=a%20b"
+        );
+        assert_eq!(
+            canonicalize_url("https://web.archive.org/web/2024/https://x.com/a"),
+            "https://web.archive.org/web/2024/https://x.com/a"
+        );
+    }
+
+    #[test]
+    fn test_url_key_folds_scheme_and_www() {
+        assert_eq!(url_key("http://www.example.com/post/?utm_source=x"), "example.com/post");
+        assert_eq!(url_key("https://example.com/post"), "example.com/post");
+        assert_eq!(url_key("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_keeps_root_path() {
+        assert_eq!(canonicalize_url("https://example.com/"), "https://example.com/");
+    }
+
+    #[test]
+    fn test_unparseable_url_is_unchanged() {
+        assert_eq!(canonicalize_url("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_find_canonical_link() {
+        let base = Url::parse("https://example.com/p/1?x=1").unwrap();
+        let html = r#"<html><head><link rel="canonical" href="/p/hello-world"></head></html>"#;
+        assert_eq!(
+            find_canonical_link(html, &base),
+            Some("https://example.com/p/hello-world".to_string())
+        );
+    }
+}
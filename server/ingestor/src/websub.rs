@@ -0,0 +1,419 @@
+//! WebSub (PubSubHubbub) push subscriptions for RSS sources whose feed advertises a hub
+//! Subscription state lives in `sources.meta.websub`; the callback is served by `server.rs`.
+//! `sources` is synced to browsers, so the per-source HMAC secret is never stored: it is
+//! derived from `WEBSUB_SECRET` and the source id whenever it is needed.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::models::{Source, SourceType};
+
+/// Lease we ask hubs for (hubs may grant less)
+const REQUESTED_LEASE_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Renew this long before the lease runs out
+const RENEW_BEFORE_SECS: i64 = 24 * 60 * 60;
+
+/// Don't re-send a subscription request that is still awaiting verification
+const PENDING_RETRY_SECS: i64 = 60 * 60;
+
+/// Range accepted for a hub-granted `hub.lease_seconds`
+const MIN_LEASE_SECS: i64 = 60;
+const MAX_LEASE_SECS: i64 = 30 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionState {
+    Pending,
+    Subscribed,
+    Denied,
+}
+
+/// Stored as `sources.meta.websub`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub hub_url: String,
+    pub topic: String,
+    pub state: SubscriptionState,
+    pub requested_at: DateTime<Utc>,
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    /// A renewal of a confirmed subscription was sent and not yet verified
+    #[serde(default)]
+    pub renewing: bool,
+}
+
+impl Subscription {
+    pub fn from_meta(meta: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(meta.get("websub")?.clone()).ok()
+    }
+
+    fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.state == SubscriptionState::Subscribed
+            && self.lease_expires_at.map(|expires| expires > now).unwrap_or(false)
+    }
+
+    /// Whether we sent a request the hub may still be verifying; the callback is
+    /// unauthenticated, so intents are only accepted while one is outstanding
+    fn awaiting_verification(&self, now: DateTime<Utc>) -> bool {
+        match self.state {
+            SubscriptionState::Pending => true,
+            SubscriptionState::Subscribed => {
+                self.renewing && now - self.requested_at <= chrono::Duration::seconds(PENDING_RETRY_SECS)
+            }
+            SubscriptionState::Denied => false,
+        }
+    }
+
+    /// Apply a hub's `subscribe` or `denied` intent. `None` when the intent doesn't match an
+    /// outstanding request (or is an unsubscribe, which we never ask for).
+    fn verified(&self, mode: &str, lease_seconds: Option<i64>, now: DateTime<Utc>) -> Option<Self> {
+        if !self.awaiting_verification(now) {
+            return None;
+        }
+
+        let mut subscription = self.clone();
+        subscription.renewing = false;
+        match mode {
+            "subscribe" => {
+                let lease_secs = lease_seconds.unwrap_or(REQUESTED_LEASE_SECS).clamp(MIN_LEASE_SECS, MAX_LEASE_SECS);
+                subscription.state = SubscriptionState::Subscribed;
+                subscription.lease_expires_at = Some(now.checked_add_signed(chrono::Duration::seconds(lease_secs))?);
+            }
+            "denied" => subscription.state = SubscriptionState::Denied,
+            _ => return None,
+        }
+        Some(subscription)
+    }
+}
+
+/// Whether the source currently receives pushes (polling can be skipped)
+pub fn has_active_lease(meta: &serde_json::Value) -> bool {
+    Subscription::from_meta(meta)
+        .map(|s| s.is_active(Utc::now()))
+        .unwrap_or(false)
+}
+
+/// The hub secret for a source: HMAC-SHA256 of the source id under the server key.
+/// Stable across renewals, so pushes signed before a renewal is confirmed still verify.
+pub fn source_secret(server_key: &str, source_id: i32) -> String {
+    let mut mac = <Hmac<sha2::Sha256> as hmac::digest::KeyInit>::new_from_slice(server_key.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(format!("websub:{}", source_id).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+pub fn callback_url(callback_base_url: &str, source_id: i32) -> String {
+    format!("{}/websub/{}", callback_base_url.trim_end_matches('/'), source_id)
+}
+
+/// Hub and topic advertised by the feed at its last poll
+fn advertised_hub(source: &Source) -> Option<(String, String)> {
+    let feed = source.meta.get("feed")?;
+    let hub = feed.get("hub_url")?.as_str()?.to_string();
+    let topic = feed
+        .get("self_url")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| source.ingest_url.clone())?;
+    Some((hub, topic))
+}
+
+/// Whether a (re)subscription request should be sent now
+fn needs_subscription(existing: Option<&Subscription>, hub_url: &str, topic: &str, now: DateTime<Utc>) -> bool {
+    let Some(existing) = existing else {
+        return true;
+    };
+    if existing.hub_url != hub_url || existing.topic != topic {
+        return true;
+    }
+
+    match existing.state {
+        SubscriptionState::Pending => now - existing.requested_at > chrono::Duration::seconds(PENDING_RETRY_SECS),
+        // A renewal awaiting verification is retried like a pending subscription
+        SubscriptionState::Subscribed => {
+            let expiring = existing
+                .lease_expires_at
+                .map(|expires| expires - now < chrono::Duration::seconds(RENEW_BEFORE_SECS))
+                .unwrap_or(true);
+            expiring && now - existing.requested_at > chrono::Duration::seconds(PENDING_RETRY_SECS)
+        }
+        // The hub refused; don't keep asking
+        SubscriptionState::Denied => false,
+    }
+}
+
+/// Subscribe (or renew) every active RSS source whose feed advertises a hub
+pub async fn renew_subscriptions(pool: &PgPool, callback_base_url: &str, server_key: &str) -> Result<usize> {
+    let sources = crate::db::get_active_sources(pool).await?;
+    let now = Utc::now();
+    let mut requested = 0;
+
+    for source in sources {
+        if source.source_type() != Some(SourceType::Rss) {
+            continue;
+        }
+        let Some((hub_url, topic)) = advertised_hub(&source) else {
+            continue;
+        };
+        let existing = Subscription::from_meta(&source.meta);
+        if !needs_subscription(existing.as_ref(), &hub_url, &topic, now) {
+            continue;
+        }
+
+        let secret = source_secret(server_key, source.id);
+        match subscribe(pool, &source, &hub_url, &topic, callback_base_url, &secret).await {
+            Ok(()) => requested += 1,
+            Err(e) => log::warn!("WebSub subscription for {} failed: {}", source.name, e),
+        }
+    }
+
+    Ok(requested)
+}
+
+/// Send a subscription request to the hub; the hub confirms asynchronously via the callback
+async fn subscribe(
+    pool: &PgPool,
+    source: &Source,
+    hub_url: &str,
+    topic: &str,
+    callback_base_url: &str,
+    secret: &str,
+) -> Result<()> {
+    let callback = callback_url(callback_base_url, source.id);
+
+    // Store the pending state first so the verification request can be matched.
+    // A renewal keeps the current lease until the hub confirms.
+    let existing = Subscription::from_meta(&source.meta)
+        .filter(|s| s.hub_url == hub_url && s.topic == topic && s.state == SubscriptionState::Subscribed);
+    let subscription = Subscription {
+        hub_url: hub_url.to_string(),
+        topic: topic.to_string(),
+        state: if existing.is_some() { SubscriptionState::Subscribed } else { SubscriptionState::Pending },
+        requested_at: Utc::now(),
+        renewing: existing.is_some(),
+        lease_expires_at: existing.and_then(|s| s.lease_expires_at),
+    };
+    crate::db::set_source_meta_key(pool, source.id, "websub", &serde_json::to_value(&subscription)?).await?;
+
+    log::info!("Subscribing to {} via hub {} for source {}", topic, hub_url, source.name);
+    let lease = REQUESTED_LEASE_SECS.to_string();
+    let response = reqwest::Client::new()
+        .post(hub_url)
+        .timeout(Duration::from_secs(30))
+        .header("User-Agent", crate::http::USER_AGENT)
+        .form(&[
+            ("hub.callback", callback.as_str()),
+            ("hub.mode", "subscribe"),
+            ("hub.topic", topic),
+            ("hub.secret", secret),
+            ("hub.lease_seconds", lease.as_str()),
+        ])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Hub responded with {}", response.status()));
+    }
+
+    Ok(())
+}
+
+/// Query parameters of a hub's verification (or denial) request
+#[derive(Debug, Deserialize)]
+pub struct VerificationRequest {
+    #[serde(rename = "hub.mode")]
+    pub mode: String,
+    #[serde(rename = "hub.topic")]
+    pub topic: String,
+    #[serde(rename = "hub.challenge")]
+    pub challenge: Option<String>,
+    #[serde(rename = "hub.lease_seconds")]
+    pub lease_seconds: Option<i64>,
+}
+
+/// Handle a hub's intent verification. Returns the challenge to echo when we confirm the intent.
+pub async fn verify_intent(pool: &PgPool, source_id: i32, request: &VerificationRequest) -> Result<Option<String>> {
+    let Some(source) = crate::db::get_source(pool, source_id).await? else {
+        return Ok(None);
+    };
+    let Some(subscription) = Subscription::from_meta(&source.meta) else {
+        return Ok(None);
+    };
+    if subscription.topic != request.topic {
+        log::warn!("WebSub verification for {} has unexpected topic {}", source.name, request.topic);
+        return Ok(None);
+    }
+
+    // We never unsubscribe, so unsubscribe intents (and any intent we didn't ask for) are refused
+    let Some(subscription) = subscription.verified(&request.mode, request.lease_seconds, Utc::now()) else {
+        log::warn!("Ignoring unexpected WebSub {} intent for {}", request.mode, source.name);
+        return Ok(None);
+    };
+    match subscription.state {
+        SubscriptionState::Denied => log::warn!("WebSub hub denied subscription for {}", source.name),
+        _ => log::info!(
+            "WebSub subscription confirmed for {} (lease until {:?})",
+            source.name,
+            subscription.lease_expires_at
+        ),
+    }
+
+    crate::db::set_source_meta_key(pool, source.id, "websub", &serde_json::to_value(&subscription)?).await?;
+    Ok(request.challenge.clone())
+}
+
+/// Check `X-Hub-Signature: <algo>=<hex hmac>` against the body
+pub fn verify_signature(secret: &str, signature_header: &str, body: &[u8]) -> bool {
+    let Some((algorithm, signature)) = signature_header.trim().split_once('=') else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+
+    match algorithm.to_lowercase().as_str() {
+        "sha1" => verify_hmac::<Hmac<sha1::Sha1>>(secret, body, &signature),
+        "sha256" => verify_hmac::<Hmac<sha2::Sha256>>(secret, body, &signature),
+        "sha384" => verify_hmac::<Hmac<sha2::Sha384>>(secret, body, &signature),
+        "sha512" => verify_hmac::<Hmac<sha2::Sha512>>(secret, body, &signature),
+        _ => false,
+    }
+}
+
+fn verify_hmac<M: Mac + hmac::digest::KeyInit>(secret: &str, body: &[u8], signature: &[u8]) -> bool {
+    let Ok(mut mac) = <M as hmac::digest::KeyInit>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(signature).is_ok()
+}
+
+/// Handle a content distribution request. Unsigned or badly signed pushes are ignored.
+pub async fn receive_push(
+    pool: &PgPool,
+    server_key: &str,
+    source_id: i32,
+    signature_header: Option<&str>,
+    body: &[u8],
+) -> Result<u64> {
+    let Some(source) = crate::db::get_source(pool, source_id).await? else {
+        return Err(anyhow!("Unknown source {}", source_id));
+    };
+    if Subscription::from_meta(&source.meta).is_none() {
+        return Err(anyhow!("Source {} has no WebSub subscription", source.name));
+    }
+
+    let secret = source_secret(server_key, source.id);
+    let signed = signature_header
+        .map(|header| verify_signature(&secret, header, body))
+        .unwrap_or(false);
+    if !signed {
+        return Err(anyhow!("Invalid WebSub signature for source {}", source.name));
+    }
+
+    crate::sources::rss::ingest_pushed_feed(pool, &source, body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(b"<feed/>");
+        let header = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verify_signature("s3cret", &header, b"<feed/>"));
+        assert!(!verify_signature("s3cret", &header, b"<feed>tampered</feed>"));
+        assert!(!verify_signature("other", &header, b"<feed/>"));
+        assert!(!verify_signature("s3cret", "md5=abcd", b"<feed/>"));
+    }
+
+    #[test]
+    fn test_source_secret() {
+        let secret = source_secret("server-key", 7);
+        assert_eq!(secret, source_secret("server-key", 7));
+        assert_ne!(secret, source_secret("server-key", 8));
+        assert_ne!(secret, source_secret("other-key", 7));
+        assert_eq!(secret.len(), 64);
+    }
+
+    #[test]
+    fn test_needs_subscription() {
+        let now = Utc::now();
+        let mut subscription = Subscription {
+            hub_url: "https://hub.example.com".to_string(),
+            topic: "https://example.com/feed".to_string(),
+            state: SubscriptionState::Subscribed,
+            requested_at: now - chrono::Duration::days(2),
+            lease_expires_at: Some(now + chrono::Duration::days(5)),
+            renewing: false,
+        };
+        let hub = "https://hub.example.com";
+        let topic = "https://example.com/feed";
+
+        assert!(needs_subscription(None, hub, topic, now));
+        assert!(!needs_subscription(Some(&subscription), hub, topic, now));
+        assert!(needs_subscription(Some(&subscription), "https://other-hub.example.com", topic, now));
+
+        subscription.lease_expires_at = Some(now + chrono::Duration::hours(2));
+        assert!(needs_subscription(Some(&subscription), hub, topic, now));
+
+        // Renewal sent, hub hasn't confirmed yet
+        subscription.requested_at = now - chrono::Duration::minutes(5);
+        assert!(!needs_subscription(Some(&subscription), hub, topic, now));
+
+        subscription.state = SubscriptionState::Denied;
+        assert!(!needs_subscription(Some(&subscription), hub, topic, now));
+    }
+
+    #[test]
+    fn test_verified_only_with_outstanding_request() {
+        let now = Utc::now();
+        let mut subscription = Subscription {
+            hub_url: "https://hub.example.com".to_string(),
+            topic: "https://example.com/feed".to_string(),
+            state: SubscriptionState::Pending,
+            requested_at: now - chrono::Duration::minutes(1),
+            lease_expires_at: None,
+            renewing: false,
+        };
+
+        assert!(subscription.verified("unsubscribe", None, now).is_none());
+        let confirmed = subscription.verified("subscribe", Some(i64::MAX), now).unwrap();
+        assert_eq!(confirmed.state, SubscriptionState::Subscribed);
+        assert_eq!(confirmed.lease_expires_at, Some(now + chrono::Duration::seconds(MAX_LEASE_SECS)));
+
+        // Confirmed and no renewal outstanding: replays and denials are ignored
+        assert!(confirmed.verified("subscribe", Some(60), now).is_none());
+        assert!(confirmed.verified("denied", None, now).is_none());
+
+        // A recent renewal can be confirmed, a stale one can't
+        subscription = confirmed;
+        subscription.renewing = true;
+        assert!(subscription.verified("subscribe", Some(-5), now).unwrap().lease_expires_at > Some(now));
+        subscription.requested_at = now - chrono::Duration::days(1);
+        assert!(subscription.verified("subscribe", None, now).is_none());
+    }
+
+    #[test]
+    fn test_active_lease() {
+        let now = Utc::now();
+        let meta = serde_json::json!({
+            "websub": {
+                "hub_url": "https://hub.example.com",
+                "topic": "https://example.com/feed",
+                "state": "subscribed",
+                "requested_at": now,
+                "lease_expires_at": now + chrono::Duration::hours(1),
+            }
+        });
+        assert!(has_active_lease(&meta));
+        assert!(!has_active_lease(&serde_json::json!({})));
+    }
+}
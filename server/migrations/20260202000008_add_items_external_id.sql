@@ -0,0 +1,32 @@
+-- Add a stable external id to items (arXiv base id, feed guid, tweet id, ...)
+-- Ingestors upsert on (source_id, external_id) and fall back to (source_id, url)
+ALTER TABLE items ADD COLUMN IF NOT EXISTS external_id TEXT;
+
+-- Backfill from raw_metadata. Only the oldest row per (source_id, external_id)
+-- gets the id so that existing URL duplicates don't break the unique index.
+WITH candidates AS (
+  SELECT
+    id,
+    source_id,
+    COALESCE(
+      regexp_replace(raw_metadata->>'arxiv_id', 'v[0-9]+$', ''),
+      raw_metadata->>'feed_id'
+    ) AS external_id,
+    created_at
+  FROM items
+  WHERE external_id IS NULL
+),
+ranked AS (
+  SELECT
+    id,
+    external_id,
+    ROW_NUMBER() OVER (PARTITION BY source_id, external_id ORDER BY created_at) AS rn
+  FROM candidates
+  WHERE external_id IS NOT NULL
+)
+UPDATE items
+SET external_id = ranked.external_id
+FROM ranked
+WHERE items.id = ranked.id AND ranked.rn = 1;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_items_external_id ON items(source_id, external_id);
@@ -0,0 +1,10 @@
+-- Allow podcast and video content alongside papers, newsletters, blogs and tweets
+-- Episode/enclosure details live in items.raw_metadata.media
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_medium_check;
+ALTER TABLE sources ADD CONSTRAINT sources_medium_check
+    CHECK (medium IN ('paper', 'newsletter', 'blog', 'tweet', 'podcast', 'video'));
+
+ALTER TABLE items DROP CONSTRAINT IF EXISTS items_source_type_check;
+ALTER TABLE items ADD CONSTRAINT items_source_type_check
+    CHECK (source_type IN ('paper', 'newsletter', 'blog', 'tweet', 'podcast', 'video'));
@@ -0,0 +1,6 @@
+-- Email newsletters read from an mbox file, Maildir directory or IMAP mailbox
+-- Sync position lives in sources.meta.email_state
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email'));
@@ -0,0 +1,6 @@
+-- Mastodon accounts, hashtags and lists (REST API) or ActivityPub outboxes
+-- Statuses are stored as tweet-medium items; sync position lives in sources.meta.mastodon_state
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon'));
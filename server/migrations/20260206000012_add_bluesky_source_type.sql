@@ -0,0 +1,6 @@
+-- Bluesky author feeds and custom feeds over AT Protocol XRPC
+-- Posts are stored as tweet-medium items keyed by their AT URI
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky'));
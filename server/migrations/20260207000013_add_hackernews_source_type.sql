@@ -0,0 +1,7 @@
+-- Hacker News stories via the Algolia search and Firebase APIs
+-- Stories already stored from another source get raw_metadata.hackernews instead of a new item;
+-- the lookup by url across sources uses idx_items_url from the items migration
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews'));
@@ -0,0 +1,6 @@
+-- GitHub releases, organisation repositories and repository searches
+-- Stars, language and release tag are kept in items.raw_metadata
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github'));
@@ -0,0 +1,8 @@
+-- Hugging Face Hub models, datasets and Daily Papers
+-- Daily Papers are matched to existing arXiv items through raw_metadata->>'arxiv_id'
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface'));
+
+CREATE INDEX IF NOT EXISTS idx_items_arxiv_id ON items ((raw_metadata->>'arxiv_id'));
@@ -0,0 +1,6 @@
+-- YouTube channel and playlist feeds, stored with the video medium
+-- Caption text (when enabled) is kept in items.body
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface', 'youtube'));
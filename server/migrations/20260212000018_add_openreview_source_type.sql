@@ -0,0 +1,6 @@
+-- OpenReview conference submissions with reviews and decisions
+-- Submissions are cross-linked to arXiv items with the same title
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface', 'youtube', 'openreview'));
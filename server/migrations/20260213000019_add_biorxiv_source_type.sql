@@ -0,0 +1,6 @@
+-- bioRxiv / medRxiv preprints from the details API
+-- The version-less DOI is the external id; raw_metadata.version tracks revisions
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface', 'youtube', 'openreview', 'biorxiv'));
@@ -0,0 +1,6 @@
+-- Listing pages scraped with CSS selectors, for sites without a feed
+-- A hash of the extracted entries in sources.meta.scrape_state skips unchanged pages
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface', 'youtube', 'openreview', 'biorxiv', 'edgar', 'scrape'));
@@ -0,0 +1,6 @@
+-- Sitemap-driven sources for sites without a feed
+-- New URLs are fetched once and their page metadata stored as items
+
+ALTER TABLE sources DROP CONSTRAINT IF EXISTS sources_type_check;
+ALTER TABLE sources ADD CONSTRAINT sources_type_check
+    CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface', 'youtube', 'openreview', 'biorxiv', 'edgar', 'scrape', 'sitemap'));
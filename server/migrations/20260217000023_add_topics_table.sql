@@ -0,0 +1,57 @@
+-- Topic taxonomy shared by the ingestor's topic extraction and the web /topics page
+-- An item gets a topic when its title, summary or body contains one of the topic's keywords or aliases
+
+CREATE TABLE IF NOT EXISTS topics (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    color TEXT NOT NULL DEFAULT 'gray',
+    aliases TEXT[] NOT NULL DEFAULT '{}',
+    keywords TEXT[] NOT NULL DEFAULT '{}',
+    position INTEGER NOT NULL DEFAULT 0,
+    active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_topics_active ON topics(active);
+
+-- The ingestor's default taxonomy (DEFAULT_TOPICS in ingestor/src/topics.rs)
+INSERT INTO topics (name, description, color, aliases, keywords, position) VALUES
+    ('LLM', 'Large language models, prompting and chat assistants', 'blue',
+     ARRAY['large language models', 'language models'],
+     ARRAY['llm', 'large language model', 'gpt', 'transformer', 'bert', 't5', 'llama', 'claude', 'chatgpt', 'prompt engineering'], 1),
+    ('RL', 'Reinforcement learning, reward modelling and preference optimisation', 'orange',
+     ARRAY['reinforcement learning'],
+     ARRAY['reinforcement learning', 'rl', 'rlhf', 'reward model', 'policy gradient', 'q-learning', 'dpo'], 2),
+    ('Multimodal', 'Vision, image, video and other non-text modalities', 'pink',
+     ARRAY['vision', 'computer vision'],
+     ARRAY['multimodal', 'vision', 'image', 'video', 'dall-e', 'clip', 'visual', 'ocr', 'object detection'], 3),
+    ('Systems', 'Infrastructure, inference, serving and MLOps', 'gray',
+     ARRAY['infrastructure', 'mlops'],
+     ARRAY['infrastructure', 'mlops', 'systems', 'deployment', 'production', 'scalability', 'distributed', 'gpu', 'vram', 'quantization', 'inference', 'serving'], 4),
+    ('Alignment', 'Safety, interpretability, ethics and bias', 'red',
+     ARRAY['ai safety', 'safety'],
+     ARRAY['alignment', 'safety', 'ethics', 'fairness', 'bias', 'hallucination', 'interpretability', 'explainability', 'responsible ai', 'agi'], 5),
+    ('Agents', 'Autonomous agents, tool use and robotics', 'purple',
+     ARRAY['ai agents', 'autonomous agents'],
+     ARRAY['agent', 'autonomous', 'robotics', 'automation', 'action planning', 'tool use', 'function calling'], 6),
+    ('Finance', 'Markets, trading and quantitative finance', 'green',
+     ARRAY['markets', 'quant finance'],
+     ARRAY['finance', 'trading', 'market', 'stock', 'portfolio', 'investment', 'risk', 'quant', 'algorithmic'], 7),
+    ('Open Source', 'Open models, libraries and the community around them', 'teal',
+     ARRAY['oss', 'open-source'],
+     ARRAY['open source', 'hugging face', 'pytorch', 'tensorflow', 'community', 'huggingface'], 8),
+    ('Search', 'Retrieval, RAG, embeddings and vector search', 'cyan',
+     ARRAY['retrieval', 'rag'],
+     ARRAY['retrieval', 'rag', 'search', 'knowledge base', 'vector database', 'embedding', 'semantic search'], 9),
+    ('Data', 'Datasets, annotation and synthetic data', 'yellow',
+     ARRAY['datasets'],
+     ARRAY['dataset', 'data', 'annotation', 'labeling', 'synthetic data', 'pretraining'], 10),
+    ('Optimization', 'Training, fine-tuning and optimisation methods', 'messenger',
+     ARRAY['training', 'fine-tuning'],
+     ARRAY['optimization', 'training', 'fine-tuning', 'finetuning', 'learning rate', 'gradient', 'loss'], 11),
+    ('Applications', 'Products, tools and real-world use cases', 'linkedin',
+     ARRAY['use cases', 'products'],
+     ARRAY['application', 'use case', 'product', 'tool', 'plugin', 'extension', 'integration'], 12)
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,4 @@
+-- Stored URLs keep their scheme and `www.`; deduplication compares them without either
+-- Matches urls::URL_KEY_SQL in the ingestor
+
+CREATE INDEX IF NOT EXISTS idx_items_url_key ON items ((regexp_replace(url, '^https?://(www\.)?', '')));
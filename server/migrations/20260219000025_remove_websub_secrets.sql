@@ -0,0 +1,7 @@
+-- WebSub hub secrets are derived from WEBSUB_SECRET instead of stored in sources.meta,
+-- which is synced to browsers. Drop the stored copies and expire the leases so every
+-- subscription is renewed with its derived secret on the next daemon cycle.
+
+UPDATE sources
+SET meta = jsonb_set(meta, '{websub}', ((meta->'websub') - 'secret') || '{"lease_expires_at": null}'::JSONB)
+WHERE meta->'websub' ? 'secret';
@@ -0,0 +1,12 @@
+-- Cross-source lookups (Hugging Face daily papers, OpenReview) match raw_metadata.arxiv_id
+-- against version-less ids. Items stored before arXiv ids were split from their version
+-- still carry `2401.12345v2`; move the suffix to `arxiv_version`. The lookup is served by
+-- idx_items_arxiv_id from the Hugging Face migration.
+
+UPDATE items
+SET raw_metadata = raw_metadata
+    || jsonb_build_object(
+        'arxiv_id', substring(raw_metadata->>'arxiv_id' FROM '^(.+)v[0-9]+$'),
+        'arxiv_version', COALESCE(NULLIF(raw_metadata->'arxiv_version', 'null'::JSONB), to_jsonb(substring(raw_metadata->>'arxiv_id' FROM 'v([0-9]+)$')::INTEGER))
+    )
+WHERE raw_metadata->>'arxiv_id' ~ '^.+v[0-9]+$';
@@ -0,0 +1,6 @@
+-- OpenReview notes are matched to arXiv items by title, lowercased with whitespace collapsed
+-- Matches db::NORMALIZED_TITLE_SQL in the ingestor
+
+CREATE INDEX IF NOT EXISTS idx_items_arxiv_normalized_title
+ON items ((lower(btrim(regexp_replace(title, '\s+', ' ', 'g')))))
+WHERE raw_metadata ? 'arxiv_id';
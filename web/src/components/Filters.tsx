@@ -30,6 +30,8 @@ const SOURCE_TYPES = [
   { value: 'newsletter', label: 'Newsletters', icon: '📧' },
   { value: 'blog', label: 'Blogs', icon: '✍️' },
   { value: 'tweet', label: 'Tweets', icon: '🐦' },
+  { value: 'podcast', label: 'Podcasts', icon: '🎙️' },
+  { value: 'video', label: 'Videos', icon: '🎬' },
 ];
 
 export default function Filters({ onFilterChange, initialFilters }: FiltersProps) {
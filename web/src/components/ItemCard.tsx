@@ -24,6 +24,10 @@ function getSourceIcon(sourceType: string): string {
       return '✍️';
     case 'tweet':
       return '🐦';
+    case 'podcast':
+      return '🎙️';
+    case 'video':
+      return '🎬';
     default:
       return '📌';
   }
@@ -39,6 +43,10 @@ function getSourceTypeLabel(sourceType: string): string {
       return 'Blog';
     case 'newsletter':
       return 'Newsletter';
+    case 'podcast':
+      return 'Podcast';
+    case 'video':
+      return 'Video';
     default:
       return 'Other';
   }
@@ -54,6 +62,10 @@ function getSourceTypeColor(sourceType: string): string {
       return 'orange';
     case 'newsletter':
       return 'green';
+    case 'podcast':
+      return 'pink';
+    case 'video':
+      return 'red';
     default:
       return 'gray';
   }
@@ -151,6 +163,9 @@ export default function ItemCard({ item }: ItemCardProps) {
   }
 
   const categories = item.rawMetadata?.categories;
+  const hackernews = item.rawMetadata?.hackernews;
+  const huggingface = item.rawMetadata?.huggingface;
+  const openreview = item.rawMetadata?.openreview;
   const sourceTypeColor = getSourceTypeColor(item.sourceType);
 
   return (
@@ -211,6 +226,52 @@ export default function ItemCard({ item }: ItemCardProps) {
               {categories[0]}
             </Badge>
           )}
+          {hackernews?.discussion_url && (
+            <Badge
+              fontSize="xs"
+              px={2}
+              py={0.5}
+              rounded="md"
+              bg="orange.900"
+              color="orange.200"
+              cursor="pointer"
+              title="Open the Hacker News discussion"
+              onClick={() => window.open(hackernews.discussion_url, '_blank')}
+            >
+              ▲ {hackernews.points} · {hackernews.comments} comments
+            </Badge>
+          )}
+          {huggingface?.paper_url && (
+            <Badge
+              fontSize="xs"
+              px={2}
+              py={0.5}
+              rounded="md"
+              bg="yellow.900"
+              color="yellow.200"
+              cursor="pointer"
+              title="Open the Hugging Face paper page"
+              onClick={() => window.open(huggingface.paper_url, '_blank')}
+            >
+              🤗 {huggingface.upvotes} upvotes
+            </Badge>
+          )}
+          {openreview?.forum_url && (
+            <Badge
+              fontSize="xs"
+              px={2}
+              py={0.5}
+              rounded="md"
+              bg="purple.900"
+              color="purple.200"
+              cursor="pointer"
+              title="Open the OpenReview forum"
+              onClick={() => window.open(openreview.forum_url, '_blank')}
+            >
+              {openreview.decision ?? openreview.venue ?? 'OpenReview'}
+              {openreview.rating_avg != null && ` · ${openreview.rating_avg}`}
+            </Badge>
+          )}
           <Text fontSize="xs" color="gray.500" ml="auto">
             {formatDate(item.publishedAt)}
           </Text>
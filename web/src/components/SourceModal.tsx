@@ -122,6 +122,19 @@ useEffect(() => {
                     <option value="rss">RSS</option>
                     <option value="twitter_api">Twitter API</option>
                     <option value="manual">Manual</option>
+                    <option value="email">Email</option>
+                    <option value="mastodon">Mastodon</option>
+                    <option value="bluesky">Bluesky</option>
+                    <option value="hackernews">Hacker News</option>
+                    <option value="reddit">Reddit</option>
+                    <option value="github">GitHub</option>
+                    <option value="huggingface">Hugging Face</option>
+                    <option value="youtube">YouTube</option>
+                    <option value="openreview">OpenReview</option>
+                    <option value="biorxiv">bioRxiv / medRxiv</option>
+                    <option value="edgar">SEC EDGAR</option>
+                    <option value="scrape">Web scrape</option>
+                    <option value="sitemap">Sitemap</option>
                   </NativeSelect.Field>
                   <NativeSelect.Indicator />
                 </NativeSelect.Root>
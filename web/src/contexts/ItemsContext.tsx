@@ -64,7 +64,7 @@ export function ItemsProvider({ children }: { children: ReactNode }) {
       const cutoffIso = new Date(Date.now() - 7 * 24 * 60 * 60 * 1000).toISOString();
 
       let completedShapes = new Set<string>();
-      const totalShapes = 3; // items, sources, item_likes only (removed item_topics)
+      const totalShapes = 4; // items, sources, topics, item_likes (removed item_topics)
       const BATCH_SIZE = 1000;
 
       const syncTimeout = setTimeout(() => {
@@ -314,6 +314,28 @@ export function ItemsProvider({ children }: { children: ReactNode }) {
         }
       });
 
+      // Sync the topic taxonomy (small, no batching needed)
+      const topicRows: any[] = [];
+
+      const topicsStream = new ShapeStream({
+        url: `${baseUrl}?table=topics`,
+      });
+
+      topicsStream.subscribe(async (messages) => {
+        for (const message of messages) {
+          if (message.headers?.control === 'up-to-date') {
+            await flushBatch('topics', topicRows.splice(0), pg);
+            console.log(`[ItemsSync] topics complete`);
+            onShapeComplete('topics');
+            return;
+          }
+
+          if (message.value) {
+            topicRows.push(message.value);
+          }
+        }
+      });
+
       // Sync item_likes with batching
       let likesBatch: any[] = [];
       let likesCount = 0;
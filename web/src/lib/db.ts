@@ -1,10 +1,10 @@
 // web/src/lib/db.ts
 import { PGlite } from '@electric-sql/pglite';
 import { drizzle } from 'drizzle-orm/pglite';
-import { papers, sources, items, itemTopics, itemLikes } from './schema';
+import { papers, sources, items, topics, itemTopics, itemLikes } from './schema';
 
 // Schema version - increment when schema changes to force client DB reset
-const SCHEMA_VERSION = 2;
+const SCHEMA_VERSION = 18;
 
 // Singleton-style promises so we only initialise once per tab
 let pglitePromise: Promise<PGlite> | null = null;
@@ -86,8 +86,8 @@ export function getDb() {
         CREATE TABLE IF NOT EXISTS sources (
           id SERIAL PRIMARY KEY,
           name TEXT NOT NULL,
-          type TEXT NOT NULL CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual')),
-          medium TEXT NOT NULL CHECK (medium IN ('paper', 'newsletter', 'blog', 'tweet')),
+          type TEXT NOT NULL CHECK (type IN ('arxiv', 'rss', 'twitter_api', 'manual', 'email', 'mastodon', 'bluesky', 'hackernews', 'reddit', 'github', 'huggingface', 'youtube', 'openreview', 'biorxiv', 'edgar', 'scrape', 'sitemap')),
+          medium TEXT NOT NULL CHECK (medium IN ('paper', 'newsletter', 'blog', 'tweet', 'podcast', 'video')),
           ingest_url TEXT,
           active BOOLEAN DEFAULT true,
           frequency TEXT,
@@ -99,7 +99,8 @@ export function getDb() {
         CREATE TABLE IF NOT EXISTS items (
           id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
           source_id INTEGER,
-          source_type TEXT NOT NULL CHECK (source_type IN ('paper', 'newsletter', 'blog', 'tweet')),
+          source_type TEXT NOT NULL CHECK (source_type IN ('paper', 'newsletter', 'blog', 'tweet', 'podcast', 'video')),
+          external_id TEXT,
           title TEXT NOT NULL,
           url TEXT NOT NULL,
           summary TEXT,
@@ -111,6 +112,19 @@ export function getDb() {
           updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
         );
 
+        CREATE TABLE IF NOT EXISTS topics (
+          id SERIAL PRIMARY KEY,
+          name TEXT NOT NULL UNIQUE,
+          description TEXT,
+          color TEXT NOT NULL DEFAULT 'gray',
+          aliases TEXT[] NOT NULL DEFAULT '{}',
+          keywords TEXT[] NOT NULL DEFAULT '{}',
+          position INTEGER NOT NULL DEFAULT 0,
+          active BOOLEAN NOT NULL DEFAULT true,
+          created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+          updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        );
+
         CREATE TABLE IF NOT EXISTS item_topics (
           id SERIAL PRIMARY KEY,
           item_id UUID NOT NULL,
@@ -135,6 +149,7 @@ export function getDb() {
         CREATE INDEX IF NOT EXISTS idx_items_source_type ON items(source_type);
         CREATE INDEX IF NOT EXISTS idx_items_url ON items(url);
         CREATE INDEX IF NOT EXISTS idx_items_topics_gin ON items USING GIN(topics);
+        CREATE INDEX IF NOT EXISTS idx_topics_active ON topics(active);
         CREATE INDEX IF NOT EXISTS idx_item_topics_item_id ON item_topics(item_id);
         CREATE INDEX IF NOT EXISTS idx_item_topics_topic ON item_topics(topic);
         CREATE INDEX IF NOT EXISTS idx_item_likes_user_id ON item_likes(user_id);
@@ -147,7 +162,7 @@ export function getDb() {
         CREATE INDEX IF NOT EXISTS idx_item_likes_user_item ON item_likes(user_id, item_id);
       `);
 
-      return drizzle(pg, { schema: { papers, sources, items, itemTopics, itemLikes } });
+      return drizzle(pg, { schema: { papers, sources, items, topics, itemTopics, itemLikes } });
     })();
   }
   return dbPromise;
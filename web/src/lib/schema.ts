@@ -14,6 +14,26 @@ import {
 export interface ItemRawMetadata {
   categories?: string[];
   authors?: string[];
+  // Hacker News discussion attached by a hackernews source
+  hackernews?: {
+    hn_id: number;
+    points: number;
+    comments: number;
+    discussion_url: string;
+  };
+  // Daily Papers upvotes attached by a huggingface source
+  huggingface?: {
+    paper_url: string;
+    upvotes: number;
+    comments: number;
+  };
+  // OpenReview submission linked by an openreview source
+  openreview?: {
+    forum_url: string;
+    venue: string | null;
+    decision: string | null;
+    rating_avg: number | null;
+  };
   [key: string]: any;
 }
 
@@ -53,8 +73,8 @@ export const papers = pgTable('papers', {
 export const sources = pgTable('sources', {
   id: serial('id').primaryKey().notNull(),
   name: text('name').notNull(),
-  type: text('type').notNull(), // 'arxiv' | 'rss' | 'twitter_api' | 'manual'
-  medium: text('medium').notNull(), // 'paper' | 'newsletter' | 'blog' | 'tweet'
+  type: text('type').notNull(), // 'arxiv' | 'rss' | 'twitter_api' | 'manual' | 'email' | 'mastodon' | 'bluesky' | 'hackernews' | 'reddit' | 'github' | 'huggingface' | 'youtube' | 'openreview' | 'biorxiv' | 'edgar' | 'scrape' | 'sitemap'
+  medium: text('medium').notNull(), // 'paper' | 'newsletter' | 'blog' | 'tweet' | 'podcast' | 'video'
   ingestUrl: text('ingest_url'),
   active: boolean('active').notNull().default(true),
   frequency: text('frequency'),
@@ -67,7 +87,8 @@ export const sources = pgTable('sources', {
 export const items = pgTable('items', {
   id: uuid('id').primaryKey().notNull(),
   sourceId: integer('source_id').notNull(),
-  sourceType: text('source_type').notNull(), // 'paper' | 'newsletter' | 'blog' | 'tweet'
+  sourceType: text('source_type').notNull(), // 'paper' | 'newsletter' | 'blog' | 'tweet' | 'podcast' | 'video'
+  externalId: text('external_id'), // stable id from the source (arXiv id, feed guid, tweet id)
   title: text('title').notNull(),
   url: text('url').notNull(),
   summary: text('summary'),
@@ -79,6 +100,20 @@ export const items = pgTable('items', {
   updatedAt: timestamp('updated_at', { mode: 'date' }).notNull(),
 });
 
+// Topics - taxonomy shared with the ingestor's topic extraction
+export const topics = pgTable('topics', {
+  id: serial('id').primaryKey().notNull(),
+  name: text('name').notNull(),
+  description: text('description'),
+  color: text('color').notNull(), // Chakra color scheme
+  aliases: text('aliases').array().notNull(),
+  keywords: text('keywords').array().notNull(),
+  position: integer('position').notNull(),
+  active: boolean('active').notNull().default(true),
+  createdAt: timestamp('created_at', { mode: 'date' }).notNull(),
+  updatedAt: timestamp('updated_at', { mode: 'date' }).notNull(),
+});
+
 // Item topics - topics associated with items
 export const itemTopics = pgTable('item_topics', {
   id: serial('id').primaryKey().notNull(),
@@ -0,0 +1,43 @@
+// web/src/lib/topics.ts
+import type { InferSelectModel } from 'drizzle-orm';
+import { asc, eq } from 'drizzle-orm';
+import { getDb, getPGlite } from './db';
+import { topics } from './schema';
+import { logger } from '@/utils/logger';
+
+export type Topic = InferSelectModel<typeof topics>;
+
+/**
+ * Get the active topics of the taxonomy, in display order
+ */
+export async function getTopics(): Promise<Topic[]> {
+  try {
+    const db = await getDb();
+    return await db
+      .select()
+      .from(topics)
+      .where(eq(topics.active, true))
+      .orderBy(asc(topics.position), asc(topics.name));
+  } catch (err) {
+    logger.warn('Failed to get topics:', err);
+    return [];
+  }
+}
+
+/**
+ * Count synced items per topic name
+ */
+export async function getTopicItemCounts(): Promise<Record<string, number>> {
+  try {
+    const pg = await getPGlite();
+    const result = await pg.query<{ topic: string; count: number }>(`
+      SELECT topic, COUNT(*)::int as count
+      FROM items, unnest(topics) as topic
+      GROUP BY topic
+    `);
+    return Object.fromEntries(result.rows.map(r => [r.topic, r.count]));
+  } catch (err) {
+    logger.warn('Failed to count items per topic:', err);
+    return {};
+  }
+}
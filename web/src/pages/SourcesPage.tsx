@@ -24,6 +24,19 @@ import {
   MessageSquare,
   AlertCircle,
   Newspaper,
+  Mail,
+  AtSign,
+  Cloud,
+  Flame,
+  MessagesSquare,
+  GitBranch,
+  Smile,
+  MonitorPlay,
+  MessageSquareText,
+  Dna,
+  Landmark,
+  Code,
+  Network,
   Link as LinkIcon,
   Plus,
   Edit,
@@ -45,6 +58,19 @@ const SOURCE_TYPE_ICONS: Record<string, React.ComponentType<any>> = {
   rss: Rss,
   twitter_api: MessageSquare,
   manual: FileText,
+  email: Mail,
+  mastodon: AtSign,
+  bluesky: Cloud,
+  hackernews: Flame,
+  reddit: MessagesSquare,
+  github: GitBranch,
+  huggingface: Smile,
+  youtube: MonitorPlay,
+  openreview: MessageSquareText,
+  biorxiv: Dna,
+  edgar: Landmark,
+  scrape: Code,
+  sitemap: Network,
 };
 
 const SOURCE_TYPE_COLORS: Record<string, string> = {
@@ -52,6 +78,19 @@ const SOURCE_TYPE_COLORS: Record<string, string> = {
   rss: 'blue',
   twitter_api: 'cyan',
   manual: 'orange',
+  email: 'green',
+  mastodon: 'purple',
+  bluesky: 'blue',
+  hackernews: 'orange',
+  reddit: 'red',
+  github: 'gray',
+  huggingface: 'yellow',
+  youtube: 'red',
+  openreview: 'purple',
+  biorxiv: 'teal',
+  edgar: 'green',
+  scrape: 'cyan',
+  sitemap: 'orange',
 };
 
 const MEDIUM_COLORS: Record<string, string> = {
@@ -1,98 +1,94 @@
-import { Box, Container, Flex, Text, Heading, Badge, Grid } from '@chakra-ui/react';
-import { TrendingUp, BarChart3 } from 'lucide-react';
+import { useEffect, useState } from 'react';
+import { Box, Container, Flex, Text, Heading, Badge, SimpleGrid, Spinner, HStack } from '@chakra-ui/react';
+import { TrendingUp } from 'lucide-react';
+import { useItems } from '@/contexts/ItemsContext';
+import { getTopics, getTopicItemCounts, type Topic } from '@/lib/topics';
 
 export default function TopicsPage() {
+  const { loading: syncLoading } = useItems();
+  const [topics, setTopics] = useState<Topic[]>([]);
+  const [counts, setCounts] = useState<Record<string, number>>({});
+  const [isLoading, setIsLoading] = useState(true);
+
+  useEffect(() => {
+    if (syncLoading) return;
+
+    (async () => {
+      setIsLoading(true);
+      const [taxonomy, itemCounts] = await Promise.all([getTopics(), getTopicItemCounts()]);
+      setTopics(taxonomy);
+      setCounts(itemCounts);
+      setIsLoading(false);
+    })();
+  }, [syncLoading]);
+
   return (
     <Container maxW="6xl" py={12}>
-      <Box
-        bg="gray.900"
-        borderWidth="1px"
-        borderColor="gray.700"
-        rounded="lg"
-        p={16}
-        textAlign="center"
-      >
-        <Flex direction="column" align="center" gap={6}>
-          <Box p={5} bg="gray.800" rounded="full" borderWidth="2px" borderColor="gray.600">
-            <TrendingUp size={56} color="var(--chakra-colors-green.400)" strokeWidth={1.5} />
-          </Box>
-          <Box>
-            <Heading
-              size="lg"
-              mb={3}
-              bgGradient="linear(to-r, green.400, teal.400)"
-              bgClip="text"
-              fontWeight="black"
-            >
-              Trends & Analytics
-            </Heading>
-            <Text color="gray.400" fontSize="md" maxW="lg" lineHeight="tall" mb={4}>
-              Discover trending AI topics, popular research areas, and emerging themes in artificial intelligence.
-            </Text>
-          </Box>
+      <Flex direction="column" align="center" textAlign="center" gap={4} mb={10}>
+        <Box p={5} bg="gray.800" rounded="full" borderWidth="2px" borderColor="gray.600">
+          <TrendingUp size={40} color="var(--chakra-colors-green.400)" strokeWidth={1.5} />
+        </Box>
+        <Heading
+          size="lg"
+          bgGradient="linear(to-r, green.400, teal.400)"
+          bgClip="text"
+          fontWeight="black"
+        >
+          Topics
+        </Heading>
+        <Text color="gray.400" fontSize="md" maxW="lg" lineHeight="tall">
+          The taxonomy items are tagged with. An item gets a topic when its title, summary or body mentions one of the topic's keywords or aliases.
+        </Text>
+      </Flex>
 
-          {/* Feature Preview */}
-          <Grid
-            templateColumns={{ base: '1fr', md: 'repeat(3, 1fr)' }}
-            gap={4}
-            w="full"
-            maxW="2xl"
-            mt={4}
-          >
-            <Box
-              p={4}
-              bg="gray.800"
-              borderWidth="1px"
-              borderColor="gray.700"
-              rounded="lg"
-            >
-              <BarChart3 size={24} color="var(--chakra-colors-blue-400)" style={{ marginBottom: '8px' }} />
-              <Text fontSize="sm" color="gray.300" fontWeight="semibold" mb={1}>
-                Topic Trends
-              </Text>
-              <Text fontSize="xs" color="gray.500">
-                Visualize trending topics over time
-              </Text>
-            </Box>
-            <Box
-              p={4}
-              bg="gray.800"
-              borderWidth="1px"
-              borderColor="gray.700"
-              rounded="lg"
-            >
-              <TrendingUp size={24} color="var(--chakra-colors-green-400)" style={{ marginBottom: '8px' }} />
-              <Text fontSize="sm" color="gray.300" fontWeight="semibold" mb={1}>
-                Hot Topics
-              </Text>
-              <Text fontSize="xs" color="gray.500">
-                Most discussed AI themes
-              </Text>
-            </Box>
+      {isLoading || syncLoading ? (
+        <Flex justify="center" py={12}>
+          <Spinner size="lg" color="green.400" />
+        </Flex>
+      ) : topics.length === 0 ? (
+        <Text color="gray.500" textAlign="center">
+          No topics defined yet.
+        </Text>
+      ) : (
+        <SimpleGrid columns={{ base: 1, md: 2, lg: 3 }} gap={4}>
+          {topics.map((topic) => (
             <Box
-              p={4}
-              bg="gray.800"
+              key={topic.id}
+              p={5}
+              bg="gray.900"
               borderWidth="1px"
               borderColor="gray.700"
               rounded="lg"
             >
-              <Box mb={2}>
-                <Badge colorScheme="purple" fontSize="xs">NEW</Badge>
-              </Box>
-              <Text fontSize="sm" color="gray.300" fontWeight="semibold" mb={1}>
-                Emerging Areas
-              </Text>
-              <Text fontSize="xs" color="gray.500">
-                Discover new research areas
-              </Text>
+              <Flex justify="space-between" align="center" mb={2}>
+                <Badge colorScheme={topic.color} fontSize="sm" px={2} py={0.5}>
+                  {topic.name}
+                </Badge>
+                <Text fontSize="xs" color="gray.500">
+                  {counts[topic.name] ?? 0} items
+                </Text>
+              </Flex>
+              {topic.description && (
+                <Text fontSize="sm" color="gray.300" mb={3}>
+                  {topic.description}
+                </Text>
+              )}
+              {topic.aliases.length > 0 && (
+                <Text fontSize="xs" color="gray.500" mb={2}>
+                  Also known as: {topic.aliases.join(', ')}
+                </Text>
+              )}
+              <HStack gap={1} flexWrap="wrap">
+                {topic.keywords.map((keyword) => (
+                  <Badge key={keyword} variant="outline" colorScheme="gray" fontSize="2xs">
+                    {keyword}
+                  </Badge>
+                ))}
+              </HStack>
             </Box>
-          </Grid>
-
-          <Badge colorScheme="green" variant="subtle" px={3} py={1} rounded="full" fontSize="sm">
-            Coming Soon
-          </Badge>
-        </Flex>
-      </Box>
+          ))}
+        </SimpleGrid>
+      )}
     </Container>
   );
 }